@@ -0,0 +1,262 @@
+//! Minimal updates between successive frames
+//!
+//! Redrawing every line of a frame is cheap on a local terminal but slow over serial consoles or high-latency SSH
+//! sessions. A `Differ` compares each frame against the last and reports only the spans of text that changed, which a
+//! driver can write after moving the cursor to their position.
+//!
+//! Frames are plain text with lines separated by `\n`; escape sequences aren't understood, and would be compared like
+//! any other text.
+
+use width;
+
+/// Unchanged runs up to this many cells are rewritten rather than skipped, since moving the cursor costs about as much
+const MERGE_GAP: usize = 4;
+
+/// A span of text to be written over the previous frame
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Change<'a> {
+    /// Line of the frame, counting from 0
+    pub line: usize,
+    /// Terminal cells between the start of the line and the start of the span
+    pub column: usize,
+    pub text: &'a str,
+    /// Whether the rest of the line after `text` must be erased, because the previous frame's line was longer
+    pub clear: bool,
+}
+
+/// Tracks the previous frame to compute the changes needed to draw the next
+///
+/// # Examples
+/// ```
+/// # use yapb::diff::{Change, Differ};
+/// let mut differ = Differ::new();
+/// assert_eq!(differ.update("copying [##      ] 25%").len(), 1);
+/// assert_eq!(
+///     differ.update("copying [###     ] 37%"),
+///     [
+///         Change { line: 0, column: 11, text: "#", clear: false },
+///         Change { line: 0, column: 19, text: "37", clear: false },
+///     ]
+/// );
+/// assert_eq!(differ.update("copying [###     ] 37%"), []);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Differ {
+    previous: String,
+}
+
+impl Differ {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes needed to turn the previous frame into `frame`, which becomes the previous frame
+    pub fn update<'a>(&mut self, frame: &'a str) -> Vec<Change<'a>> {
+        let changes = diff(&self.previous, frame);
+        self.previous.clear();
+        self.previous.push_str(frame);
+        changes
+    }
+
+    /// Forget the previous frame, e.g. after the screen was cleared, so the next update redraws everything
+    pub fn reset(&mut self) {
+        self.previous.clear();
+    }
+}
+
+/// Changes needed to turn `previous` into `current`
+///
+/// Lines of `previous` beyond the end of `current` are reported as empty changes that clear the whole line.
+///
+/// # Examples
+/// ```
+/// # use yapb::diff::{diff, Change};
+/// assert_eq!(
+///     diff("a 10%\nb 20%", "a 25%"),
+///     [
+///         Change { line: 0, column: 2, text: "25", clear: false },
+///         Change { line: 1, column: 0, text: "", clear: true },
+///     ]
+/// );
+/// ```
+pub fn diff<'a>(previous: &str, current: &'a str) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    let mut old_lines = previous.split('\n');
+    for (line, new) in current.split('\n').enumerate() {
+        diff_line(&mut changes, line, old_lines.next().unwrap_or(""), new);
+    }
+    let first = current.split('\n').count();
+    for (i, old) in old_lines.enumerate() {
+        if !old.is_empty() {
+            changes.push(Change {
+                line: first + i,
+                column: 0,
+                text: "",
+                clear: true,
+            });
+        }
+    }
+    changes
+}
+
+fn diff_line<'a>(changes: &mut Vec<Change<'a>>, line: usize, old: &str, new: &'a str) {
+    let mut old_clusters = width::clusters(old).peekable();
+    let mut old_column = 0;
+    let (mut column, mut offset) = (0, 0);
+    // Change being accumulated, and the offset in `new` at which its text begins
+    let mut pending: Option<(Change<'a>, usize)> = None;
+    for (cluster, cells) in width::clusters(new) {
+        // Find the cluster of the old line starting at the same column, if any
+        while old_column < column {
+            match old_clusters.next() {
+                Some((_, w)) => old_column += w,
+                None => break,
+            }
+        }
+        let same = old_column == column && old_clusters.peek().is_some_and(|&(c, _)| c == cluster);
+        if !same {
+            let begin = match pending {
+                Some((ref change, begin))
+                    if column - change.column - width::str_width(change.text) <= MERGE_GAP =>
+                {
+                    begin
+                }
+                _ => {
+                    changes.extend(pending.take().map(|(change, _)| change));
+                    offset
+                }
+            };
+            let change = Change {
+                line,
+                column: pending.map_or(column, |(change, _)| change.column),
+                text: &new[begin..offset + cluster.len()],
+                clear: false,
+            };
+            pending = Some((change, begin));
+        }
+        column += cells;
+        offset += cluster.len();
+    }
+    let mut last = pending.map(|(change, _)| change);
+    if width::str_width(old) > column {
+        // Erase the old line's excess, from the end of the last change if it reaches the end of the line
+        match last {
+            Some(ref mut change) if change.column + width::str_width(change.text) == column => {
+                change.clear = true
+            }
+            _ => {
+                changes.extend(last.take());
+                last = Some(Change {
+                    line,
+                    column,
+                    text: "",
+                    clear: true,
+                });
+            }
+        }
+    }
+    changes.extend(last);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        assert_eq!(diff("same\nx", "same\nx"), []);
+        assert_eq!(
+            diff("abc", "\nxyz"),
+            [
+                Change {
+                    line: 0,
+                    column: 0,
+                    text: "",
+                    clear: true
+                },
+                Change {
+                    line: 1,
+                    column: 0,
+                    text: "xyz",
+                    clear: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shorter() {
+        assert_eq!(
+            diff("12.5 MiB/s", "9 MiB/s"),
+            [Change {
+                line: 0,
+                column: 0,
+                text: "9 MiB/s",
+                clear: true
+            }]
+        );
+        assert_eq!(
+            diff("done!", "done"),
+            [Change {
+                line: 0,
+                column: 4,
+                text: "",
+                clear: true
+            }]
+        );
+    }
+
+    #[test]
+    fn merge() {
+        // Nearby changes are combined, distant ones kept separate
+        assert_eq!(
+            diff("a-b", "x-y"),
+            [Change {
+                line: 0,
+                column: 0,
+                text: "x-y",
+                clear: false
+            }]
+        );
+        assert_eq!(
+            diff("a-----b", "x-----y"),
+            [
+                Change {
+                    line: 0,
+                    column: 0,
+                    text: "x",
+                    clear: false
+                },
+                Change {
+                    line: 0,
+                    column: 6,
+                    text: "y",
+                    clear: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn wide() {
+        // A wide character shifts the columns of what follows
+        assert_eq!(
+            diff("ab cd", "文 cd"),
+            [Change {
+                line: 0,
+                column: 0,
+                text: "文",
+                clear: false
+            }]
+        );
+        assert_eq!(
+            diff("文 cd", "a文cd"),
+            [Change {
+                line: 0,
+                column: 0,
+                text: "a文",
+                clear: false
+            }]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Helpers to display compact human-readable durations
+
+use std::fmt::{self, Display};
+use std::time;
+
+use prefix;
+
+/// Helper struct to format a duration using its two most significant units, e.g. `1h 23m`, `3m 12s`, or `450ms`
+///
+/// Less significant units are truncated rather than rounded, so a countdown never shows a time that hasn't been
+/// reached.
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::Human;
+/// # use std::time::Duration;
+/// assert_eq!(format!("{}", Human(Duration::from_secs(5000))), "1h 23m");
+/// assert_eq!(format!("{}", Human(Duration::from_secs(192))), "3m 12s");
+/// assert_eq!(format!("{}", Human(Duration::from_secs(12))), "12s");
+/// assert_eq!(format!("{}", Human(Duration::from_millis(450))), "450ms");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Human(pub time::Duration);
+impl Display for Human {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_human(f, self.0, false)
+    }
+}
+
+/// Like `Human`, but always 7 characters wide for durations under 100 days, for stable layouts
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::FixedHuman;
+/// # use std::time::Duration;
+/// assert_eq!(format!("[{}]", FixedHuman(Duration::from_secs(3720))), "[ 1h 02m]");
+/// assert_eq!(format!("[{}]", FixedHuman(Duration::from_secs(12))), "[    12s]");
+/// assert_eq!(format!("[{}]", FixedHuman(Duration::from_millis(45))), "[   45ms]");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct FixedHuman(pub time::Duration);
+impl Display for FixedHuman {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_human(f, self.0, true)
+    }
+}
+
+/// Helper struct to format a duration as a clock reading, e.g. `01:23:45`, or `2 days, 01:23:45` beyond a day
+///
+/// Durations under a day always produce exactly 8 characters.
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::Hms;
+/// # use std::time::Duration;
+/// assert_eq!(format!("{}", Hms(Duration::from_secs(192))), "00:03:12");
+/// assert_eq!(format!("{}", Hms(Duration::from_secs(5025))), "01:23:45");
+/// assert_eq!(format!("{}", Hms(Duration::from_secs(86400 + 5025))), "1 day, 01:23:45");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Hms(pub time::Duration);
+impl Display for Hms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let days = secs / (24 * 60 * 60);
+        match days {
+            0 => {}
+            1 => f.write_str("1 day, ")?,
+            _ => write!(f, "{} days, ", days)?,
+        }
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            secs / (60 * 60) % 24,
+            secs / 60 % 60,
+            secs % 60
+        )
+    }
+}
+
+/// Helper struct to format a duration as an approximate phrase, e.g. `about 3 minutes` or `less than a minute`
+///
+/// Suited to end-user-facing interfaces, where a precise estimate would imply false accuracy.
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::Fuzzy;
+/// # use std::time::Duration;
+/// assert_eq!(format!("{}", Fuzzy(Duration::from_secs(20))), "less than a minute");
+/// assert_eq!(format!("{}", Fuzzy(Duration::from_secs(190))), "about 3 minutes");
+/// assert_eq!(format!("{}", Fuzzy(Duration::from_secs(4000))), "about an hour");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Fuzzy(pub time::Duration);
+impl Display for Fuzzy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const MINUTE: f64 = 60.0;
+        const HOUR: f64 = 60.0 * MINUTE;
+        const DAY: f64 = 24.0 * HOUR;
+
+        let secs = self.0.as_secs_f64();
+        if secs < 45.0 {
+            f.write_str("less than a minute")
+        } else if secs < 90.0 {
+            f.write_str("about a minute")
+        } else if secs < 45.0 * MINUTE {
+            write!(f, "about {} minutes", (secs / MINUTE).round())
+        } else if secs < 90.0 * MINUTE {
+            f.write_str("about an hour")
+        } else if secs < 22.0 * HOUR {
+            write!(f, "about {} hours", (secs / HOUR).round())
+        } else if secs < 36.0 * HOUR {
+            f.write_str("about a day")
+        } else {
+            write!(f, "about {} days", (secs / DAY).round())
+        }
+    }
+}
+
+/// Helper struct to format a duration in the ISO 8601 form, e.g. `PT1H23M45S`, for machine-readable output
+///
+/// Days are not used, since their length is ambiguous across daylight saving changes; longer durations are expressed
+/// in hours. Fractional seconds are written with as many digits as needed.
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::Iso8601;
+/// # use std::time::Duration;
+/// assert_eq!(format!("{}", Iso8601(Duration::from_secs(5025))), "PT1H23M45S");
+/// assert_eq!(format!("{}", Iso8601(Duration::from_millis(1500))), "PT1.5S");
+/// assert_eq!(format!("{}", Iso8601(Duration::from_secs(0))), "PT0S");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Iso8601(pub time::Duration);
+impl Display for Iso8601 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let nanos = self.0.subsec_nanos();
+        f.write_str("PT")?;
+        let (hours, minutes, seconds) = (secs / (60 * 60), secs / 60 % 60, secs % 60);
+        if hours != 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes != 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if seconds != 0 || nanos != 0 || secs == 0 {
+            write!(f, "{}", seconds)?;
+            if nanos != 0 {
+                let fraction = format!("{:09}", nanos);
+                write!(f, ".{}", fraction.trim_end_matches('0'))?;
+            }
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Helper struct to format a duration in seconds with an SI prefix and 3 significant figures, e.g. `1.23 ms` or
+/// `450 µs`, for benchmark-style output
+///
+/// Durations of a minute or more are formatted as by `Human`.
+///
+/// # Examples
+/// ```
+/// # use yapb::duration::Adaptive;
+/// # use std::time::Duration;
+/// assert_eq!(format!("{}", Adaptive(Duration::from_micros(1234))), "1.23 ms");
+/// assert_eq!(format!("{}", Adaptive(Duration::from_micros(450))), "450 µs");
+/// assert_eq!(format!("{}", Adaptive(Duration::from_millis(12345))), "12.3 s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Adaptive(pub time::Duration);
+impl Display for Adaptive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs_f64();
+        if secs >= 60.0 {
+            return Human(self.0).fmt(f);
+        }
+        if secs == 0.0 {
+            return f.write_str("0.00 s");
+        }
+        let (value, prefix) = prefix::si(secs);
+        prefix::fmt_sigfigs(f, value, 3)?;
+        write!(f, " {}s", prefix)
+    }
+}
+
+fn fmt_human(f: &mut fmt::Formatter, duration: time::Duration, fixed: bool) -> fmt::Result {
+    const UNITS: [(u64, &str); 4] = [(24 * 60 * 60, "d"), (60 * 60, "h"), (60, "m"), (1, "s")];
+
+    let secs = duration.as_secs();
+    if secs == 0 {
+        let millis = duration.subsec_millis();
+        return if fixed {
+            write!(f, "{:>5}ms", millis)
+        } else {
+            write!(f, "{}ms", millis)
+        };
+    }
+    let i = UNITS.iter().position(|&(size, _)| secs >= size).unwrap();
+    let (size, unit) = UNITS[i];
+    let major = secs / size;
+    match UNITS.get(i + 1) {
+        Some(&(minor_size, minor_unit)) => {
+            let minor = secs % size / minor_size;
+            if fixed {
+                write!(f, "{:>2}{} {:02}{}", major, unit, minor, minor_unit)
+            } else {
+                write!(f, "{}{} {}{}", major, unit, minor, minor_unit)
+            }
+        }
+        None if fixed => write!(f, "{:>6}{}", major, unit),
+        None => write!(f, "{}{}", major, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn human() {
+        let h = |secs| format!("{}", Human(time::Duration::from_secs(secs)));
+        assert_eq!(h(0), "0ms");
+        assert_eq!(h(60), "1m 0s");
+        assert_eq!(h(3 * 86400 + 7200 + 59), "3d 2h");
+        let fixed = |secs| format!("{}", FixedHuman(time::Duration::from_secs(secs)));
+        for &secs in &[0, 1, 59, 60, 3599, 3600, 86399, 86400, 99 * 86400 - 1] {
+            assert_eq!(fixed(secs).len(), 7);
+        }
+    }
+
+    #[test]
+    fn hms() {
+        let hms = |secs| format!("{}", Hms(time::Duration::from_secs(secs)));
+        assert_eq!(hms(0), "00:00:00");
+        assert_eq!(hms(86399), "23:59:59");
+        assert_eq!(hms(3 * 86400), "3 days, 00:00:00");
+    }
+
+    #[test]
+    fn fuzzy() {
+        let fuzzy = |secs| format!("{}", Fuzzy(time::Duration::from_secs(secs)));
+        assert_eq!(fuzzy(60), "about a minute");
+        assert_eq!(fuzzy(44 * 60), "about 44 minutes");
+        assert_eq!(fuzzy(5 * 3600), "about 5 hours");
+        assert_eq!(fuzzy(30 * 3600), "about a day");
+        assert_eq!(fuzzy(36 * 3600), "about 2 days");
+    }
+
+    #[test]
+    fn iso8601() {
+        let iso = |d| format!("{}", Iso8601(d));
+        assert_eq!(iso(time::Duration::from_secs(3600)), "PT1H");
+        assert_eq!(iso(time::Duration::from_secs(26 * 3600 + 5)), "PT26H5S");
+        assert_eq!(iso(time::Duration::from_nanos(1)), "PT0.000000001S");
+    }
+
+    #[test]
+    fn adaptive() {
+        let adaptive = |d| format!("{}", Adaptive(d));
+        assert_eq!(adaptive(time::Duration::from_nanos(7)), "7.00 ns");
+        assert_eq!(adaptive(time::Duration::from_secs(0)), "0.00 s");
+        assert_eq!(adaptive(time::Duration::from_secs(90)), "1m 30s");
+    }
+}
@@ -0,0 +1,83 @@
+//! Helpers for displaying progress with `egui`
+//!
+//! GUI tools can share estimators and formatting with their command-line counterparts: `egui`'s own `ProgressBar`
+//! draws the fraction complete, labeled with text produced here, while any other widget can be shown as monospace text.
+
+extern crate egui;
+
+use std::fmt::Display;
+
+use self::egui::{ProgressBar, RichText};
+
+use duration::Human;
+use estimate::{Eta, RateEstimator};
+use prefix::Rate;
+use Throughput;
+
+/// An `egui` progress bar at `fraction`, labeled with the throughput and time remaining, e.g. `2.00 MiB/s, 12s left`
+///
+/// Parts of the label that can't be estimated yet are omitted.
+pub fn progress_bar<E: RateEstimator>(
+    fraction: f32,
+    throughput: &Throughput,
+    unit: &str,
+    eta: &Eta<E>,
+) -> ProgressBar {
+    ProgressBar::new(fraction).text(summary(throughput, unit, eta))
+}
+
+/// Throughput and time remaining, e.g. `2.00 MiB/s, 12s left`
+///
+/// # Examples
+/// ```
+/// # extern crate yapb;
+/// # use std::time::{Duration, Instant};
+/// # use yapb::*;
+/// # use yapb::estimate::Eta;
+/// # fn main() {
+/// let start = Instant::now();
+/// let mut throughput = Throughput::new(1.0, Duration::from_millis(100));
+/// let mut eta = Eta::new(MovingAverage64::new(1.0, 0.0));
+/// throughput.record(start, 0);
+/// eta.update(start, 0.0);
+/// let later = start + Duration::from_secs(1);
+/// throughput.record(later, 1 << 20);
+/// eta.update(later, 0.25);
+/// assert_eq!(yapb::egui::summary(&throughput, "B", &eta), "1.00 MiB/s, 3s left");
+/// # }
+/// ```
+pub fn summary<E: RateEstimator>(throughput: &Throughput, unit: &str, eta: &Eta<E>) -> String {
+    let mut text = String::new();
+    if throughput.get() > 0.0 {
+        text = Rate(throughput.get(), unit).to_string();
+    }
+    if let Some(remaining) = eta.remaining() {
+        if !text.is_empty() {
+            text.push_str(", ");
+        }
+        text.push_str(&format!("{} left", Human(remaining)));
+    }
+    text
+}
+
+/// Any widget formatted at `width` cells, for display in a monospace `egui::Label`
+pub fn monospace(widget: impl Display, width: usize) -> RichText {
+    RichText::new(format!("{:1$}", widget, width)).monospace()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use {Bar, MovingAverage64, Progress};
+
+    #[test]
+    fn text() {
+        let mut bar = Bar::new();
+        bar.set(0.5);
+        assert_eq!(monospace(bar, 4).text(), "██  ");
+        let throughput = Throughput::new(1.0, Duration::from_millis(100));
+        let eta = Eta::new(MovingAverage64::new(1.0, 0.0));
+        assert_eq!(summary(&throughput, "B", &eta), "");
+    }
+}
@@ -0,0 +1,248 @@
+//! Spinners built from emoji
+//!
+//! Most terminals render emoji two cells wide. The `Display` impls here honor the formatter's width in terms of
+//! terminal cells rather than `char`s, so e.g. `{:4}` pads a two-cell frame with two fill characters and keeps
+//! surrounding columns aligned.
+//!
+//! Some frames include an emoji presentation selector (U+FE0F) to request two-cell rendering of characters that
+//! default to a narrow text presentation. Terminals that ignore the selector may misalign such frames.
+
+use std::fmt::{self, Display};
+
+use width;
+use {Spinner, WidthHint};
+
+/// Write a single `char` padded by its display width
+fn pad_char(f: &mut fmt::Formatter, c: char) -> fmt::Result {
+    let mut buf = [0; 4];
+    width::pad(f, c.encode_utf8(&mut buf))
+}
+
+/// Implement `WidthHint` for spinners whose every frame is a single emoji two cells wide
+macro_rules! double_cell {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl WidthHint for $ty {
+                fn width_hint(&self, width: Option<usize>) -> usize {
+                    width.unwrap_or(0).max(2)
+                }
+            }
+        )*
+    };
+}
+
+double_cell!(Moon, Clock, Hourglass, Globe, Weather);
+
+/// A spinner that cycles through the 8 phases of the moon
+///
+/// # Examples
+/// ```
+/// # use yapb::{Spinner, emoji::Moon};
+/// let mut moon = Moon::new();
+/// moon.step(4);
+/// assert_eq!(format!("[{:3}]", moon), "[🌕 ]");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Moon {
+    state: u8,
+}
+
+const MOON_STATES: [char; 8] = ['🌑', '🌒', '🌓', '🌔', '🌕', '🌖', '🌗', '🌘'];
+
+impl Moon {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Moon {
+    fn set(&mut self, state: u32) {
+        self.state = state as u8 % MOON_STATES.len() as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count as u8) % MOON_STATES.len() as u8;
+    }
+    fn frames(&self) -> u32 {
+        MOON_STATES.len() as u32
+    }
+}
+
+impl Display for Moon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad_char(f, *unsafe {
+            MOON_STATES.get_unchecked(self.state as usize)
+        })
+    }
+}
+
+/// A spinner that cycles through 24 clock faces, advancing half an hour per frame from 12:00
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Clock {
+    state: u8,
+}
+
+const CLOCK_STATES: [char; 24] = [
+    '🕛', '🕧', '🕐', '🕜', '🕑', '🕝', '🕒', '🕞', '🕓', '🕟', '🕔', '🕠', '🕕', '🕡', '🕖', '🕢',
+    '🕗', '🕣', '🕘', '🕤', '🕙', '🕥', '🕚', '🕦',
+];
+
+impl Clock {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Clock {
+    fn set(&mut self, state: u32) {
+        self.state = (state % CLOCK_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let state =
+            (u32::from(self.state) + count % CLOCK_STATES.len() as u32) % CLOCK_STATES.len() as u32;
+        self.state = state as u8;
+    }
+    fn frames(&self) -> u32 {
+        CLOCK_STATES.len() as u32
+    }
+}
+
+impl Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad_char(f, *unsafe {
+            CLOCK_STATES.get_unchecked(self.state as usize)
+        })
+    }
+}
+
+/// A spinner that cycles through 2 states with an hourglass flipping over
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Hourglass {
+    state: u8,
+}
+
+const HOURGLASS_STATES: [&str; 2] = ["⏳", "⌛"];
+
+impl Hourglass {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Hourglass {
+    fn set(&mut self, state: u32) {
+        self.state = (state % HOURGLASS_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = HOURGLASS_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        HOURGLASS_STATES.len() as u32
+    }
+}
+
+impl Display for Hourglass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        width::pad(f, unsafe {
+            HOURGLASS_STATES.get_unchecked(self.state as usize)
+        })
+    }
+}
+
+/// A spinner that cycles through 3 states with a rotating globe
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Globe {
+    state: u8,
+}
+
+const GLOBE_STATES: [&str; 3] = ["🌍", "🌎", "🌏"];
+
+impl Globe {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Globe {
+    fn set(&mut self, state: u32) {
+        self.state = (state % GLOBE_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = GLOBE_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        GLOBE_STATES.len() as u32
+    }
+}
+
+impl Display for Globe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        width::pad(f, unsafe {
+            GLOBE_STATES.get_unchecked(self.state as usize)
+        })
+    }
+}
+
+/// A spinner that cycles through 12 states with the weather clouding over into a storm and clearing again
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Weather {
+    state: u8,
+}
+
+const WEATHER_STATES: [&str; 12] = [
+    "☀\u{FE0F}",
+    "🌤\u{FE0F}",
+    "⛅",
+    "🌥\u{FE0F}",
+    "☁\u{FE0F}",
+    "🌧\u{FE0F}",
+    "⛈\u{FE0F}",
+    "🌧\u{FE0F}",
+    "☁\u{FE0F}",
+    "🌥\u{FE0F}",
+    "⛅",
+    "🌤\u{FE0F}",
+];
+
+impl Weather {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Weather {
+    fn set(&mut self, state: u32) {
+        self.state = (state % WEATHER_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = WEATHER_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        WEATHER_STATES.len() as u32
+    }
+}
+
+impl Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        width::pad(f, unsafe {
+            WEATHER_STATES.get_unchecked(self.state as usize)
+        })
+    }
+}
@@ -0,0 +1,732 @@
+//! Estimators for predicting how long remaining work will take
+//!
+//! `Eta` tracks progress over time and delegates the smoothing of its rate to any `RateEstimator`, allowing the
+//! trade-off between responsiveness and stability to be chosen to suit the workload.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use duration::Human;
+use {MovingAverage, MovingAverage64, TimeWeightedAverage};
+
+/// Estimates the time remaining until some progress reaches completion
+///
+/// # Examples
+/// ```
+/// # use yapb::estimate::{Eta, Kalman};
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut eta = Eta::new(Kalman::new(1e-4, 1e-2));
+/// eta.update(start, 0.0);
+/// eta.update(start + Duration::from_secs(1), 0.25);
+/// assert_eq!(eta.remaining(), Some(Duration::from_secs(3)));
+/// assert_eq!(format!("{}", eta), "3s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Eta<E> {
+    estimator: E,
+    last: Option<(Instant, f64)>,
+    interval: Duration,
+}
+
+impl<E: RateEstimator> Eta<E> {
+    pub fn new(estimator: E) -> Self {
+        Self {
+            estimator,
+            last: None,
+            interval: Duration::from_secs(0),
+        }
+    }
+
+    /// Record that progress has reached `fraction`, in [0, 1], as of `now`
+    ///
+    /// Updates should be made at a steady cadence, e.g. once per redraw, rather than on every increment of progress.
+    pub fn update(&mut self, now: Instant, fraction: f64) {
+        if let Some((time, previous)) = self.last {
+            let elapsed = now.saturating_duration_since(time);
+            if elapsed == Duration::from_secs(0) {
+                return;
+            }
+            self.estimator
+                .update((fraction - previous) / elapsed.as_secs_f64(), elapsed);
+            self.interval = elapsed;
+        }
+        self.last = Some((now, fraction));
+    }
+
+    /// Estimated time until completion, if one can be made
+    pub fn remaining(&self) -> Option<Duration> {
+        let (_, fraction) = self.last?;
+        if self.interval == Duration::from_secs(0) {
+            return None;
+        }
+        self.estimator.time_to(1.0 - fraction, self.interval)
+    }
+
+    /// Smoothed rate of progress, in fractions of the total per second
+    pub fn rate(&self) -> f64 {
+        self.estimator.rate()
+    }
+
+    /// Progress as of the most recent update, if any
+    pub fn fraction(&self) -> Option<f64> {
+        self.last.map(|(_, fraction)| fraction)
+    }
+
+    /// Access the rate estimator
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+}
+
+/// Displays the estimated time remaining, e.g. `3m 12s`, or nothing if no estimate can be made
+impl<E: RateEstimator> fmt::Display for Eta<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.remaining() {
+            Some(x) => Human(x).fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Smoothing strategies for the rate of progress used by `Eta`
+pub trait RateEstimator {
+    /// Incorporate a rate, in units per second, measured over `elapsed`
+    fn update(&mut self, rate: f64, elapsed: Duration);
+
+    /// Current estimate of the rate, in units per second
+    fn rate(&self) -> f64;
+
+    /// Estimated time needed to accumulate `remaining` units, given that samples arrive every `interval`
+    fn time_to(&self, remaining: f64, interval: Duration) -> Option<Duration> {
+        let _ = interval;
+        if remaining <= 0.0 {
+            return Some(Duration::from_secs(0));
+        }
+        // Fails for negative, infinite, or unrepresentably large times
+        Duration::try_from_secs_f64(remaining / self.rate()).ok()
+    }
+}
+
+impl RateEstimator for MovingAverage {
+    fn update(&mut self, rate: f64, _: Duration) {
+        MovingAverage::update(self, rate as f32);
+    }
+    fn rate(&self) -> f64 {
+        f64::from(self.get())
+    }
+}
+
+impl RateEstimator for MovingAverage64 {
+    fn update(&mut self, rate: f64, _: Duration) {
+        MovingAverage64::update(self, rate);
+    }
+    fn rate(&self) -> f64 {
+        self.get()
+    }
+}
+
+impl RateEstimator for TimeWeightedAverage {
+    fn update(&mut self, rate: f64, elapsed: Duration) {
+        TimeWeightedAverage::update(self, rate as f32, elapsed);
+    }
+    fn rate(&self) -> f64 {
+        f64::from(self.get())
+    }
+}
+
+impl RateEstimator for Holt {
+    fn update(&mut self, rate: f64, _: Duration) {
+        Holt::update(self, rate);
+    }
+    fn rate(&self) -> f64 {
+        self.level()
+    }
+    fn time_to(&self, remaining: f64, interval: Duration) -> Option<Duration> {
+        Holt::time_to(self, remaining, interval)
+    }
+}
+
+/// One-dimensional Kalman filter modeling the rate as a random walk observed through noisy measurements
+///
+/// Compared to exponential smoothing, this adapts its responsiveness to how consistent the measurements have been,
+/// which damps the oscillation of estimates derived from erratic sources such as flaky networks.
+#[derive(Debug, Copy, Clone)]
+pub struct Kalman {
+    process_noise: f64,
+    measurement_noise: f64,
+    estimate: f64,
+    variance: f64,
+    initialized: bool,
+}
+
+impl Kalman {
+    /// `process_noise` is the variance of the true rate's drift per second, and `measurement_noise` is the variance of
+    /// the error in each measurement
+    ///
+    /// Raising `process_noise` relative to `measurement_noise` makes the estimate more responsive and less smooth.
+    pub fn new(process_noise: f64, measurement_noise: f64) -> Self {
+        Self {
+            process_noise,
+            measurement_noise,
+            estimate: 0.0,
+            variance: 0.0,
+            initialized: false,
+        }
+    }
+
+    /// Incorporate a measurement taken `elapsed` after the previous one
+    pub fn update(&mut self, measurement: f64, elapsed: Duration) {
+        if !self.initialized {
+            self.estimate = measurement;
+            self.variance = self.measurement_noise;
+            self.initialized = true;
+            return;
+        }
+        // Predict
+        self.variance += self.process_noise * elapsed.as_secs_f64();
+        // Correct
+        let gain = self.variance / (self.variance + self.measurement_noise);
+        self.estimate += gain * (measurement - self.estimate);
+        self.variance *= 1.0 - gain;
+    }
+
+    /// Current estimate
+    pub fn get(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Variance of the current estimate
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+}
+
+impl RateEstimator for Kalman {
+    fn update(&mut self, rate: f64, elapsed: Duration) {
+        Kalman::update(self, rate, elapsed);
+    }
+    fn rate(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Double exponential (Holt) smoothing, which tracks both the level of a series and its trend
+///
+/// Fed with periodic throughput samples, this can anticipate accelerating or decelerating progress rather than
+/// assuming the current rate will hold.
+///
+/// # Examples
+/// ```
+/// # use yapb::estimate::Holt;
+/// # use std::time::Duration;
+/// let mut rate = Holt::new(0.5, 0.5);
+/// // Throughput, sampled once per second, is steadily increasing
+/// for &x in &[10.0, 20.0, 30.0, 40.0, 50.0, 60.0] {
+///     rate.update(x);
+/// }
+/// assert!(rate.trend() > 9.0);
+/// let eta = rate.time_to(150.0, Duration::from_secs(1)).unwrap();
+/// // Sooner than the 2.5s that a constant rate of 60/s would suggest
+/// assert!(eta < Duration::from_millis(2500));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Holt {
+    alpha: f64,
+    beta: f64,
+    level: f64,
+    trend: f64,
+    initialized: bool,
+}
+
+impl Holt {
+    /// `alpha` and `beta` are in (0, 1] describing how responsive the level and trend respectively are to each update
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        Self {
+            alpha,
+            beta,
+            level: 0.0,
+            trend: 0.0,
+            initialized: false,
+        }
+    }
+
+    /// Update with a new sample
+    pub fn update(&mut self, value: f64) {
+        if !self.initialized {
+            self.level = value;
+            self.initialized = true;
+            return;
+        }
+        let previous = self.level;
+        self.level = self.alpha * value + (1.0 - self.alpha) * (self.level + self.trend);
+        self.trend = self.beta * (self.level - previous) + (1.0 - self.beta) * self.trend;
+    }
+
+    /// Smoothed value of the most recent sample
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Smoothed change in value per sample
+    pub fn trend(&self) -> f64 {
+        self.trend
+    }
+
+    /// Predict the value `steps` samples in the future
+    pub fn forecast(&self, steps: f64) -> f64 {
+        self.level + steps * self.trend
+    }
+
+    /// Estimate the time needed to accumulate `remaining` units, if the samples are rates in units per second taken
+    /// every `interval`
+    ///
+    /// Returns `None` if the projected rate falls to zero before the work is complete.
+    pub fn time_to(&self, remaining: f64, interval: Duration) -> Option<Duration> {
+        if remaining <= 0.0 {
+            return Some(Duration::from_secs(0));
+        }
+        // Solve remaining = level * t + acceleration * t^2 / 2 for the smallest positive t
+        let acceleration = self.trend / interval.as_secs_f64();
+        let t = if acceleration.abs() < 1e-12 {
+            remaining / self.level
+        } else {
+            let discriminant = self.level * self.level + 2.0 * acceleration * remaining;
+            if discriminant < 0.0 {
+                return None;
+            }
+            (-self.level + discriminant.sqrt()) / acceleration
+        };
+        Duration::try_from_secs_f64(t).ok()
+    }
+}
+
+/// Median of the most recent rate measurements
+///
+/// Isolated spikes and dips, such as those caused by garbage collection pauses or bursty I/O, have no effect on the
+/// median until they make up half of the window, so estimates stay steady where an average would jump around.
+///
+/// # Examples
+/// ```
+/// # use yapb::estimate::MedianRate;
+/// let mut rate = MedianRate::new(5);
+/// for &x in &[10.0, 11.0, 500.0, 9.0, 0.0] {
+///     rate.update(x);
+/// }
+/// assert_eq!(rate.get(), 10.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MedianRate {
+    samples: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl MedianRate {
+    /// Construct an estimator reporting the median of the `capacity` most recent measurements
+    ///
+    /// # Panics
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity != 0, "capacity must be nonzero");
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Incorporate a measurement, displacing the oldest if the window is full
+    pub fn update(&mut self, measurement: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(measurement);
+    }
+
+    /// Median of the measurements in the window, or 0 if there are none
+    ///
+    /// For an even number of measurements, the mean of the middle two is reported.
+    pub fn get(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let mut sorted = self.samples.iter().cloned().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let middle = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[middle]
+        } else {
+            (sorted[middle - 1] + sorted[middle]) / 2.0
+        }
+    }
+}
+
+impl RateEstimator for MedianRate {
+    fn update(&mut self, rate: f64, _: Duration) {
+        MedianRate::update(self, rate);
+    }
+    fn rate(&self) -> f64 {
+        self.get()
+    }
+}
+
+/// Predicts completion by fitting a least-squares line through recent (time, progress) observations
+///
+/// Unlike `Eta`, which divides the remaining work by a smoothed rate, this considers the overall trajectory of the
+/// window, which makes it less sensitive to jitter in individual measurements and to uneven update intervals.
+///
+/// # Examples
+/// ```
+/// # use yapb::estimate::Regression;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut prediction = Regression::new(16);
+/// for i in 0..3 {
+///     prediction.update(start + Duration::from_secs(i), 0.1 * (i + 1) as f64);
+/// }
+/// let now = start + Duration::from_secs(2);
+/// let remaining = prediction.remaining(now).unwrap();
+/// assert!((remaining.as_secs_f64() - 7.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Regression {
+    points: VecDeque<(Instant, f64)>,
+    capacity: usize,
+}
+
+impl Regression {
+    /// Construct a predictor fitting the `capacity` most recent observations
+    ///
+    /// # Panics
+    /// If `capacity` is less than two.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity >= 2, "capacity must be at least two");
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record that progress has reached `fraction`, in [0, 1], as of `now`, displacing the oldest observation if the
+    /// window is full
+    pub fn update(&mut self, now: Instant, fraction: f64) {
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+        }
+        self.points.push_back((now, fraction));
+    }
+
+    /// Fitted rate of progress, in fractions of the total per second, if at least two distinct times were observed
+    pub fn rate(&self) -> Option<f64> {
+        self.fit().map(|(_, slope)| slope)
+    }
+
+    /// Predicted time at which progress reaches 1, if progress is increasing
+    pub fn completion(&self) -> Option<Instant> {
+        let (intercept, slope) = self.fit()?;
+        let t = (1.0 - intercept) / slope;
+        if !(slope > 0.0 && t.is_finite()) {
+            return None;
+        }
+        let origin = self.points.front()?.0;
+        if t <= 0.0 {
+            return Some(origin);
+        }
+        origin.checked_add(Duration::try_from_secs_f64(t).ok()?)
+    }
+
+    /// Predicted time remaining as of `now`, if progress is increasing
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        Some(self.completion()?.saturating_duration_since(now))
+    }
+
+    /// Forget all observations
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Intercept and slope of progress against seconds since the oldest observation
+    fn fit(&self) -> Option<(f64, f64)> {
+        let origin = self.points.front()?.0;
+        let n = self.points.len() as f64;
+        let points = || {
+            self.points
+                .iter()
+                .map(move |&(t, y)| (t.saturating_duration_since(origin).as_secs_f64(), y))
+        };
+        let (sum_x, sum_y) = points().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let (covariance, variance) = points().fold((0.0, 0.0), |(c, v), (x, y)| {
+            (
+                c + (x - mean_x) * (y - mean_y),
+                v + (x - mean_x) * (x - mean_x),
+            )
+        });
+        if variance == 0.0 {
+            return None;
+        }
+        let slope = covariance / variance;
+        Some((mean_y - slope * mean_x, slope))
+    }
+}
+
+/// Displays the time remaining as a range, e.g. `3–5 min`, reflecting how consistent recent progress has been
+///
+/// The bounds are the times needed to finish at the mean of the recent rate measurements plus and minus some number of
+/// standard deviations, so erratic progress produces a visibly wider range rather than a falsely precise estimate.
+///
+/// # Examples
+/// ```
+/// # use yapb::estimate::EtaRange;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut eta = EtaRange::new(8);
+/// for (i, &x) in [0.0, 0.1, 0.15, 0.25, 0.3].iter().enumerate() {
+///     eta.update(start + Duration::from_secs(i as u64), x);
+/// }
+/// assert_eq!(format!("{}", eta), "7–14 s");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EtaRange {
+    rates: VecDeque<f64>,
+    capacity: usize,
+    deviations: f64,
+    last: Option<(Instant, f64)>,
+}
+
+impl EtaRange {
+    /// Construct an estimator considering the `capacity` most recent rate measurements
+    ///
+    /// # Panics
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity != 0, "capacity must be nonzero");
+        Self {
+            rates: VecDeque::with_capacity(capacity),
+            capacity,
+            deviations: 1.0,
+            last: None,
+        }
+    }
+
+    /// Widen or narrow the range to span `deviations` standard deviations either side of the mean rate, rather than 1
+    pub fn with_deviations(self, deviations: f64) -> Self {
+        Self { deviations, ..self }
+    }
+
+    /// Record that progress has reached `fraction`, in [0, 1], as of `now`
+    ///
+    /// Updates should be made at a steady cadence, e.g. once per redraw, rather than on every increment of progress.
+    pub fn update(&mut self, now: Instant, fraction: f64) {
+        if let Some((time, previous)) = self.last {
+            let elapsed = now.saturating_duration_since(time);
+            if elapsed == Duration::from_secs(0) {
+                return;
+            }
+            if self.rates.len() == self.capacity {
+                self.rates.pop_front();
+            }
+            self.rates
+                .push_back((fraction - previous) / elapsed.as_secs_f64());
+        }
+        self.last = Some((now, fraction));
+    }
+
+    /// Shortest and longest plausible times until completion, if an estimate can be made
+    ///
+    /// The upper bound is `None` if progress may plausibly have stopped altogether.
+    pub fn range(&self) -> Option<(Duration, Option<Duration>)> {
+        let (_, fraction) = self.last?;
+        if self.rates.is_empty() {
+            return None;
+        }
+        let remaining = (1.0 - fraction).max(0.0);
+        let n = self.rates.len() as f64;
+        let mean = self.rates.iter().sum::<f64>() / n;
+        let variance = self
+            .rates
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / n;
+        let spread = self.deviations * variance.sqrt();
+        // Unbounded if the rate isn't positive or the time is too large to represent
+        let time = |rate: f64| {
+            if rate > 0.0 {
+                Duration::try_from_secs_f64(remaining / rate).ok()
+            } else {
+                None
+            }
+        };
+        Some((time(mean + spread)?, time(mean - spread)))
+    }
+}
+
+impl fmt::Display for EtaRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (lower, upper) = match self.range() {
+            None => return Ok(()),
+            Some(x) => x,
+        };
+        // Express both bounds in the unit suited to the larger
+        let (scale, unit) = match upper.unwrap_or(lower).as_secs_f64() {
+            x if x < 90.0 => (1.0, "s"),
+            x if x < 90.0 * 60.0 => (60.0, "min"),
+            _ => (60.0 * 60.0, "h"),
+        };
+        let lower = (lower.as_secs_f64() / scale).round();
+        match upper {
+            None => write!(f, "over {} {}", lower, unit),
+            Some(upper) => {
+                let upper = (upper.as_secs_f64() / scale).round();
+                if upper == lower {
+                    write!(f, "{} {}", lower, unit)
+                } else {
+                    write!(f, "{}–{} {}", lower, upper, unit)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kalman_damps_noise() {
+        let mut kalman = Kalman::new(1e-3, 1.0);
+        let mut ema = MovingAverage::new(0.5, 0.0);
+        let second = Duration::from_secs(1);
+        let mut kalman_swing = 0.0f64;
+        let mut ema_swing = 0.0f64;
+        for i in 0..100 {
+            let sample = if i % 2 == 0 { 9.0 } else { 11.0 };
+            let (k, e) = (kalman.get(), f64::from(ema.get()));
+            Kalman::update(&mut kalman, sample, second);
+            MovingAverage::update(&mut ema, sample as f32);
+            if i > 50 {
+                kalman_swing = kalman_swing.max((kalman.get() - k).abs());
+                ema_swing = ema_swing.max((f64::from(ema.get()) - e).abs());
+            }
+        }
+        assert!((kalman.get() - 10.0).abs() < 0.5);
+        assert!(kalman_swing < ema_swing / 4.0);
+    }
+
+    #[test]
+    fn regression_stalled() {
+        let start = Instant::now();
+        let mut prediction = Regression::new(4);
+        prediction.update(start, 0.5);
+        assert_eq!(prediction.completion(), None);
+        prediction.update(start + Duration::from_secs(1), 0.5);
+        assert_eq!(prediction.rate(), Some(0.0));
+        assert_eq!(prediction.completion(), None);
+    }
+
+    #[test]
+    fn regression_slow() {
+        let start = Instant::now();
+        let mut prediction = Regression::new(4);
+        prediction.update(start, 0.0);
+        prediction.update(start + Duration::from_secs(1), 1e-30);
+        assert_eq!(prediction.completion(), None);
+        assert_eq!(prediction.remaining(start), None);
+        // Representable as a `Duration`, but not as an `Instant`
+        prediction.clear();
+        prediction.update(start, 0.0);
+        prediction.update(start + Duration::from_secs(1), 1e-19);
+        assert_eq!(prediction.completion(), None);
+    }
+
+    #[test]
+    fn eta_range_units() {
+        let start = Instant::now();
+        let mut eta = EtaRange::new(4);
+        assert_eq!(format!("{}", eta), "");
+        eta.update(start, 0.0);
+        eta.update(start + Duration::from_secs(60), 0.2);
+        assert_eq!(format!("{}", eta), "4 min");
+        eta.update(start + Duration::from_secs(120), 0.2);
+        assert_eq!(format!("{}", eta), "over 4 min");
+    }
+
+    #[test]
+    fn eta_range_slow() {
+        let start = Instant::now();
+        let mut eta = EtaRange::new(4).with_deviations(0.0);
+        eta.update(start, 0.0);
+        eta.update(start + Duration::from_secs(1), 1e-30);
+        assert_eq!(eta.range(), None);
+        eta.to_string();
+    }
+
+    #[test]
+    fn median_even() {
+        let mut rate = MedianRate::new(3);
+        assert_eq!(rate.get(), 0.0);
+        rate.update(4.0);
+        rate.update(2.0);
+        assert_eq!(rate.get(), 3.0);
+        rate.update(8.0);
+        rate.update(16.0);
+        assert_eq!(rate.get(), 8.0);
+    }
+
+    #[test]
+    fn eta_needs_samples() {
+        let start = Instant::now();
+        let mut eta = Eta::new(MovingAverage::new(0.5, 0.0));
+        assert_eq!(eta.remaining(), None);
+        eta.update(start, 0.0);
+        assert_eq!(eta.remaining(), None);
+        eta.update(start + Duration::from_secs(2), 0.5);
+        assert_eq!(eta.remaining(), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn eta_stalled() {
+        let start = Instant::now();
+        let mut eta = Eta::new(MovingAverage::new(0.5, 0.0));
+        eta.update(start, 0.0);
+        eta.update(start + Duration::from_secs(1), 0.5);
+        for i in 2..200 {
+            eta.update(start + Duration::from_secs(i), 0.5);
+            eta.to_string();
+        }
+        assert_eq!(eta.remaining(), None);
+    }
+
+    #[test]
+    fn holt_constant() {
+        let mut holt = Holt::new(0.3, 0.3);
+        for _ in 0..10 {
+            holt.update(5.0);
+        }
+        assert_eq!(holt.level(), 5.0);
+        assert_eq!(holt.trend(), 0.0);
+        assert_eq!(
+            holt.time_to(10.0, Duration::from_secs(1)),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn holt_stalling() {
+        let mut holt = Holt::new(1.0, 1.0);
+        holt.update(2.0);
+        holt.update(1.0);
+        // Rate will reach zero after 1 second, having accumulated only half a unit
+        assert_eq!(holt.time_to(1.0, Duration::from_secs(1)), None);
+        assert!(holt.time_to(0.25, Duration::from_secs(1)).is_some());
+    }
+
+    #[test]
+    fn holt_slow() {
+        let mut holt = Holt::new(1.0, 1.0);
+        holt.update(1e-30);
+        holt.update(1e-30);
+        assert_eq!(holt.time_to(1.0, Duration::from_secs(1)), None);
+    }
+}
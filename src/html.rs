@@ -0,0 +1,188 @@
+//! HTML renderings of progress, for web dashboards and generated reports
+//!
+//! Output is a fragment with inline styles and a `yapb-*` class on the outermost element, so it can be embedded as-is
+//! or restyled by a stylesheet.
+
+use std::fmt::{self, Display, Write};
+
+use Bar;
+
+/// A `<progress>` element showing the bar's progress, with the percentage as fallback content
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// assert_eq!(
+///     html::bar(&bar).to_string(),
+///     r#"<progress class="yapb-bar" max="1" value="0.5">50%</progress>"#
+/// );
+/// ```
+pub fn bar(bar: &Bar) -> impl Display {
+    HtmlBar(bar.get())
+}
+
+struct HtmlBar(f32);
+
+impl Display for HtmlBar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            r#"<progress class="yapb-bar" max="1" value="{}">{:.0}%</progress>"#,
+            self.0,
+            self.0 * 100.0
+        )
+    }
+}
+
+/// A bar divided into consecutive segments, each given as a fraction of the whole and a CSS color
+///
+/// Useful for showing e.g. succeeded, failed, and skipped work side by side. The remainder of the bar is left empty.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// assert_eq!(
+///     html::segmented(&[(0.25, "green"), (0.5, "red")]).to_string(),
+///     "<div class=\"yapb-segmented\" style=\"display:flex\">\
+///      <span style=\"width:25%;background:green\"></span>\
+///      <span style=\"width:50%;background:red\"></span>\
+///      </div>"
+/// );
+/// ```
+pub fn segmented<'a>(segments: &'a [(f32, &'a str)]) -> impl Display + 'a {
+    Segmented(segments)
+}
+
+struct Segmented<'a>(&'a [(f32, &'a str)]);
+
+impl<'a> Display for Segmented<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(r#"<div class="yapb-segmented" style="display:flex">"#)?;
+        for &(fraction, color) in self.0 {
+            write!(
+                f,
+                r#"<span style="width:{}%;background:{}"></span>"#,
+                fraction.clamp(0.0, 1.0) * 100.0,
+                Escaped(color)
+            )?;
+        }
+        f.write_str("</div>")
+    }
+}
+
+/// A sparkline of `values` built from block elements, scaled between their minimum and maximum
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// assert_eq!(
+///     html::sparkline(&[0.0, 1.0, 2.0, 7.0]).to_string(),
+///     r#"<span class="yapb-sparkline">▁▂▃█</span>"#
+/// );
+/// ```
+pub fn sparkline(values: &[f32]) -> impl Display + '_ {
+    Sparkline(values)
+}
+
+struct Sparkline<'a>(&'a [f32]);
+
+impl<'a> Display for Sparkline<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(r#"<span class="yapb-sparkline">"#)?;
+        for level in sparkline_levels(self.0) {
+            f.write_char(level)?;
+        }
+        f.write_str("</span>")
+    }
+}
+
+/// Block elements depicting each of `values`, scaled between their minimum and maximum
+pub(crate) fn sparkline_levels(values: &[f32]) -> impl Iterator<Item = char> + '_ {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    values.iter().map(move |&x| {
+        let level = if max > min {
+            ((x - min) / (max - min) * 7.0).round() as usize
+        } else {
+            0
+        };
+        LEVELS[level.min(7)]
+    })
+}
+
+/// Any widget's terminal output at `width` cells, escaped and preformatted in a monospace `<span>`
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// assert_eq!(
+///     html::text(format_args!("<{:4}>", bar), 0).to_string(),
+///     "<span class=\"yapb\" style=\"white-space:pre;font-family:monospace\">&lt;██  &gt;</span>"
+/// );
+/// ```
+pub fn text<T: Display>(widget: T, width: usize) -> impl Display {
+    Text(widget, width)
+}
+
+struct Text<T>(T, usize);
+
+impl<T: Display> Display for Text<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = format!("{:1$}", self.0, self.1);
+        write!(
+            f,
+            r#"<span class="yapb" style="white-space:pre;font-family:monospace">{}</span>"#,
+            Escaped(&text)
+        )
+    }
+}
+
+/// Text with HTML's special characters replaced by entities
+pub(crate) struct Escaped<'a>(pub(crate) &'a str);
+
+impl<'a> Display for Escaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&#39;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(
+            segmented(&[(2.0, "\"><script>")]).to_string(),
+            "<div class=\"yapb-segmented\" style=\"display:flex\">\
+             <span style=\"width:100%;background:&quot;&gt;&lt;script&gt;\"></span></div>"
+        );
+        assert_eq!(
+            sparkline(&[3.0, 3.0]).to_string(),
+            r#"<span class="yapb-sparkline">▁▁</span>"#
+        );
+        assert_eq!(
+            sparkline(&[]).to_string(),
+            r#"<span class="yapb-sparkline"></span>"#
+        );
+        assert_eq!(
+            bar(&Bar::new()).to_string(),
+            r#"<progress class="yapb-bar" max="1" value="0">0%</progress>"#
+        );
+    }
+}
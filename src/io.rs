@@ -0,0 +1,431 @@
+//! Wrappers that report the progress of IO streams
+//!
+//! These are the only part of the crate that reads the clock, as the time of each transfer is needed to measure
+//! throughput.
+//!
+//! With the `futures` or `tokio` features, the wrappers also implement the corresponding asynchronous IO traits, and
+//! `futures` adds `ProgressStream` for streams of byte buffers.
+
+use std::io::{self, Read, Write};
+#[cfg(any(feature = "futures", feature = "tokio"))]
+use std::pin::Pin;
+#[cfg(any(feature = "futures", feature = "tokio"))]
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+
+use {Progress, Throughput};
+
+/// Reader that counts the bytes passing through it, updating a `Progress` indicator and a `Throughput`
+///
+/// # Examples
+/// ```
+/// # use std::io::Read;
+/// # use yapb::*;
+/// # use yapb::io::ProgressReader;
+/// let data = [0u8; 1024];
+/// let mut reader = ProgressReader::new(&data[..], Bar::new(), data.len() as u64);
+/// let mut buf = [0; 256];
+/// reader.read_exact(&mut buf).unwrap();
+/// assert_eq!(reader.position(), 256);
+/// assert_eq!(reader.progress().get(), 0.25);
+/// ```
+#[derive(Debug)]
+pub struct ProgressReader<R, P> {
+    inner: R,
+    tally: Tally<P>,
+}
+
+impl<R, P: Progress> ProgressReader<R, P> {
+    /// Wrap `inner`, which is expected to yield `total` bytes
+    ///
+    /// `progress` is set to the fraction of `total` read so far. Throughput is smoothed over 100ms intervals by
+    /// default; see `with_throughput`.
+    pub fn new(inner: R, progress: P, total: u64) -> Self {
+        Self {
+            inner,
+            tally: Tally::new(progress, total),
+        }
+    }
+
+    /// Use `throughput` to measure the rate of transfer
+    pub fn with_throughput(mut self, throughput: Throughput) -> Self {
+        self.tally.throughput = throughput;
+        self
+    }
+
+    /// Number of bytes read
+    pub fn position(&self) -> u64 {
+        self.tally.count
+    }
+
+    pub fn progress(&self) -> &P {
+        &self.tally.progress
+    }
+
+    pub fn throughput(&self) -> &Throughput {
+        &self.tally.throughput
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, P: Progress> Read for ProgressReader<R, P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.tally.record(n);
+        Ok(n)
+    }
+}
+
+/// Writer that counts the bytes passing through it, updating a `Progress` indicator and a `Throughput`
+///
+/// # Examples
+/// ```
+/// # use std::io::Write;
+/// # use yapb::*;
+/// # use yapb::io::ProgressWriter;
+/// let mut writer = ProgressWriter::new(Vec::new(), Bar::new(), 1024);
+/// writer.write_all(&[0; 512]).unwrap();
+/// assert_eq!(writer.position(), 512);
+/// assert_eq!(writer.progress().get(), 0.5);
+/// ```
+#[derive(Debug)]
+pub struct ProgressWriter<W, P> {
+    inner: W,
+    tally: Tally<P>,
+}
+
+impl<W, P: Progress> ProgressWriter<W, P> {
+    /// Wrap `inner`, which is expected to accept `total` bytes
+    ///
+    /// `progress` is set to the fraction of `total` written so far. Throughput is smoothed over 100ms intervals by
+    /// default; see `with_throughput`.
+    pub fn new(inner: W, progress: P, total: u64) -> Self {
+        Self {
+            inner,
+            tally: Tally::new(progress, total),
+        }
+    }
+
+    /// Use `throughput` to measure the rate of transfer
+    pub fn with_throughput(mut self, throughput: Throughput) -> Self {
+        self.tally.throughput = throughput;
+        self
+    }
+
+    /// Number of bytes written
+    pub fn position(&self) -> u64 {
+        self.tally.count
+    }
+
+    pub fn progress(&self) -> &P {
+        &self.tally.progress
+    }
+
+    pub fn throughput(&self) -> &Throughput {
+        &self.tally.throughput
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write, P: Progress> Write for ProgressWriter<W, P> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.tally.record(n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Stream of byte buffers that counts the bytes passing through it, updating a `Progress` indicator and a `Throughput`
+///
+/// Suitable for e.g. the body of an HTTP response, yielded as `Result`s of chunks.
+#[cfg(feature = "futures")]
+#[derive(Debug)]
+pub struct ProgressStream<S, P> {
+    inner: S,
+    tally: Tally<P>,
+}
+
+#[cfg(feature = "futures")]
+impl<S, P: Progress> ProgressStream<S, P> {
+    /// Wrap `inner`, which is expected to yield `total` bytes
+    pub fn new(inner: S, progress: P, total: u64) -> Self {
+        Self {
+            inner,
+            tally: Tally::new(progress, total),
+        }
+    }
+
+    /// Use `throughput` to measure the rate of transfer
+    pub fn with_throughput(mut self, throughput: Throughput) -> Self {
+        self.tally.throughput = throughput;
+        self
+    }
+
+    /// Number of bytes yielded
+    pub fn position(&self) -> u64 {
+        self.tally.count
+    }
+
+    pub fn progress(&self) -> &P {
+        &self.tally.progress
+    }
+
+    pub fn throughput(&self) -> &Throughput {
+        &self.tally.throughput
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<S, T, E, P> ::futures_core::Stream for ProgressStream<S, P>
+where
+    S: ::futures_core::Stream<Item = Result<T, E>> + Unpin,
+    T: AsRef<[u8]>,
+    P: Progress + Unpin,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let item = ready!(Pin::new(&mut this.inner).poll_next(cx));
+        if let Some(Ok(ref chunk)) = item {
+            this.tally.record(chunk.as_ref().len());
+        }
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<R: ::futures_io::AsyncRead + Unpin, P: Progress + Unpin> ::futures_io::AsyncRead
+    for ProgressReader<R, P>
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.tally.record(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<W: ::futures_io::AsyncWrite + Unpin, P: Progress + Unpin> ::futures_io::AsyncWrite
+    for ProgressWriter<W, P>
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        this.tally.record(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: ::tokio::io::AsyncRead + Unpin, P: Progress + Unpin> ::tokio::io::AsyncRead
+    for ProgressReader<R, P>
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut ::tokio::io::ReadBuf,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.tally.record(buf.filled().len() - before);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: ::tokio::io::AsyncWrite + Unpin, P: Progress + Unpin> ::tokio::io::AsyncWrite
+    for ProgressWriter<W, P>
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = ready!(Pin::new(&mut this.inner).poll_write(cx, buf))?;
+        this.tally.record(n);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Byte count and the state it drives
+#[derive(Debug)]
+struct Tally<P> {
+    progress: P,
+    throughput: Throughput,
+    count: u64,
+    total: u64,
+}
+
+impl<P: Progress> Tally<P> {
+    fn new(mut progress: P, total: u64) -> Self {
+        progress.set(0.0);
+        Self {
+            progress,
+            throughput: Throughput::new(0.3, Duration::from_millis(100)),
+            count: 0,
+            total,
+        }
+    }
+
+    fn record(&mut self, n: usize) {
+        self.count += n as u64;
+        self.throughput.record(Instant::now(), n as u64);
+        if self.total != 0 {
+            self.progress
+                .set((self.count as f64 / self.total as f64).min(1.0) as f32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bar;
+
+    #[test]
+    fn reader() {
+        let data = [1u8; 10];
+        let mut reader = ProgressReader::new(&data[..], Bar::new(), 4);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+        assert_eq!(reader.position(), 10);
+        assert_eq!(reader.progress().get(), 1.0);
+
+        let mut reader = ProgressReader::new(&data[..], Bar::new(), 0);
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(reader.progress().get(), 0.0);
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = ProgressWriter::new(Vec::new(), Bar::new(), 8);
+        writer.write_all(b"abcd").unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.progress().get(), 0.5);
+        writer.write_all(b"efghij").unwrap();
+        assert_eq!(writer.progress().get(), 1.0);
+        assert_eq!(writer.into_inner(), b"abcdefghij");
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn futures() {
+        use futures_core::Stream;
+        use futures_io::{AsyncRead, AsyncWrite};
+        use std::task::Waker;
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let data = [1u8; 4];
+        let mut reader = ProgressReader::new(&data[..], Bar::new(), 8);
+        let mut buf = [0; 3];
+        let poll = Pin::new(&mut reader).poll_read(&mut cx, &mut buf);
+        assert!(matches!(poll, Poll::Ready(Ok(3))));
+        assert_eq!(reader.position(), 3);
+
+        let mut writer = ProgressWriter::new(Vec::new(), Bar::new(), 4);
+        let poll = Pin::new(&mut writer).poll_write(&mut cx, b"ab");
+        assert!(matches!(poll, Poll::Ready(Ok(2))));
+        assert_eq!(writer.progress().get(), 0.5);
+
+        let chunks = vec![Ok::<_, ()>(vec![0; 2]), Err(()), Ok(vec![0; 6])];
+        let mut stream = ProgressStream::new(Chunks(chunks.into_iter()), Bar::new(), 8);
+        while let Poll::Ready(Some(_)) = Pin::new(&mut stream).poll_next(&mut cx) {}
+        assert_eq!(stream.position(), 8);
+        assert_eq!(stream.progress().get(), 1.0);
+    }
+
+    #[cfg(feature = "futures")]
+    struct Chunks<I>(I);
+
+    #[cfg(feature = "futures")]
+    impl<I: Iterator + Unpin> ::futures_core::Stream for Chunks<I> {
+        type Item = I::Item;
+        fn poll_next(self: Pin<&mut Self>, _: &mut Context) -> Poll<Option<I::Item>> {
+            Poll::Ready(self.get_mut().0.next())
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn tokio() {
+        use std::task::Waker;
+        use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let data = [1u8; 4];
+        let mut reader = ProgressReader::new(&data[..], Bar::new(), 4);
+        let mut storage = [0; 8];
+        let mut buf = ReadBuf::new(&mut storage);
+        let poll = Pin::new(&mut reader).poll_read(&mut cx, &mut buf);
+        assert!(matches!(poll, Poll::Ready(Ok(()))));
+        assert_eq!(reader.progress().get(), 1.0);
+
+        let mut writer = ProgressWriter::new(Vec::new(), Bar::new(), 4);
+        let poll = Pin::new(&mut writer).poll_write(&mut cx, b"abc");
+        assert!(matches!(poll, Poll::Ready(Ok(3))));
+        assert_eq!(writer.position(), 3);
+    }
+}
@@ -0,0 +1,97 @@
+//! Tracking the progress of iteration
+
+use Progress;
+
+/// Extension trait for reporting progress through an `ExactSizeIterator`
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::iter::ProgressIterator;
+/// let mut bar = Bar::new();
+/// let mut items = (0..4).progress_with(&mut bar);
+/// items.next();
+/// items.next();
+/// drop(items);
+/// assert_eq!(bar.get(), 0.5);
+/// ```
+pub trait ProgressIterator: ExactSizeIterator + Sized {
+    /// Update `progress` as items are yielded, with the iterator's current length as the total
+    fn progress_with<P: Progress>(self, progress: &mut P) -> ProgressIter<'_, Self, P>;
+}
+
+impl<I: ExactSizeIterator> ProgressIterator for I {
+    fn progress_with<P: Progress>(self, progress: &mut P) -> ProgressIter<'_, Self, P> {
+        ProgressIter::new(self, progress)
+    }
+}
+
+/// Iterator adapter that updates a `Progress` implementor as items are yielded
+#[derive(Debug)]
+pub struct ProgressIter<'a, I, P: 'a> {
+    inner: I,
+    progress: &'a mut P,
+    total: usize,
+    yielded: usize,
+}
+
+impl<'a, I: ExactSizeIterator, P: Progress> ProgressIter<'a, I, P> {
+    pub fn new(inner: I, progress: &'a mut P) -> Self {
+        progress.set(0.0);
+        Self {
+            total: inner.len(),
+            inner,
+            progress,
+            yielded: 0,
+        }
+    }
+
+    /// Number of items yielded so far
+    pub fn yielded(&self) -> usize {
+        self.yielded
+    }
+}
+
+impl<'a, I: ExactSizeIterator, P: Progress> Iterator for ProgressIter<'a, I, P> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.inner.next();
+        if item.is_some() {
+            self.yielded += 1;
+        }
+        self.progress.set(if self.total == 0 {
+            1.0
+        } else {
+            (self.yielded as f64 / self.total as f64).min(1.0) as f32
+        });
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, I: ExactSizeIterator, P: Progress> ExactSizeIterator for ProgressIter<'a, I, P> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bar;
+
+    #[test]
+    fn progress() {
+        let mut bar = Bar::new();
+        bar.set(0.5);
+        assert_eq!((0..2).progress_with(&mut bar).yielded(), 0);
+        assert_eq!(bar.get(), 0.0);
+        assert_eq!((0..3).progress_with(&mut bar).sum::<i32>(), 3);
+        assert_eq!(bar.get(), 1.0);
+        assert_eq!(
+            Vec::<()>::new().into_iter().progress_with(&mut bar).count(),
+            0
+        );
+        assert_eq!(bar.get(), 1.0);
+    }
+}
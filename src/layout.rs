@@ -0,0 +1,730 @@
+//! Arrangement of several widgets within a line of fixed width
+
+use std::fmt::{self, Alignment, Display};
+
+use width;
+use {Border, Progress, WidthHint};
+
+/// A line of widgets in which one flexible widget, typically a `Bar`, absorbs whatever width the others leave
+///
+/// Fixed widgets are padded or truncated to their declared widths, so the line occupies exactly the width it's
+/// rendered at as long as that's enough for the fixed elements.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::layout::StatusLine;
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// let spinner = Line::new();
+/// let line = StatusLine::new()
+///     .fixed(&spinner, 1)
+///     .text(" [")
+///     .flex(&bar)
+///     .text("] ")
+///     .fixed(&"50%", 4);
+/// assert_eq!(line.render(20), "| [█████▌     ] 50% ");
+/// ```
+#[derive(Clone, Default)]
+pub struct StatusLine<'a> {
+    elements: Vec<Element<'a>>,
+}
+
+#[derive(Copy, Clone)]
+enum Element<'a> {
+    Fixed(&'a dyn Display, usize),
+    Text(&'a str),
+    Flex(&'a dyn Display),
+}
+
+impl<'a> StatusLine<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `widget`, padded or truncated to `width` cells
+    pub fn fixed(mut self, widget: &'a dyn Display, width: usize) -> Self {
+        self.elements.push(Element::Fixed(widget, width));
+        self
+    }
+
+    /// Append literal text, which occupies its own width
+    pub fn text(mut self, text: &'a str) -> Self {
+        self.elements.push(Element::Text(text));
+        self
+    }
+
+    /// Append `widget`, formatted with whatever width the other elements leave
+    ///
+    /// If there are several flexible widgets, the remaining width is divided evenly between them.
+    pub fn flex(mut self, widget: &'a dyn Display) -> Self {
+        self.elements.push(Element::Flex(widget));
+        self
+    }
+
+    /// Render the line at `width` cells
+    pub fn render(&self, width: usize) -> String {
+        let fixed = self
+            .elements
+            .iter()
+            .map(|x| match *x {
+                Element::Fixed(widget, width) => Some(fit(widget, width)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut row = Row::new();
+        for (element, s) in self.elements.iter().zip(&fixed) {
+            row = row.push(match *element {
+                Element::Fixed(..) => Item::Fixed(s.as_ref().unwrap()),
+                Element::Text(ref text) => Item::Fixed(text),
+                Element::Flex(widget) => Item::Flex(widget, 1),
+            });
+        }
+        row.render(width)
+    }
+
+    /// Display the line at `width` cells
+    pub fn display(&self, width: usize) -> impl Display + '_ {
+        Rendered { line: self, width }
+    }
+}
+
+struct Rendered<'a> {
+    line: &'a StatusLine<'a>,
+    width: usize,
+}
+
+impl<'a> Display for Rendered<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.line.render(self.width))
+    }
+}
+
+/// An element of a [`Row`]
+#[derive(Copy, Clone)]
+pub enum Item<'a> {
+    /// A widget displayed at its natural width
+    Fixed(&'a dyn Display),
+    /// A widget sharing the width left over by other items in proportion to its weight
+    Flex(&'a dyn Display, u32),
+    /// Blank space of a given width
+    Gap(usize),
+}
+
+/// A sequence of items laid out across a line of a given width
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::layout::{Item, Row};
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// let row = Row::new()
+///     .push(Item::Fixed(&"copying"))
+///     .push(Item::Gap(1))
+///     .push(Item::Flex(&bar, 2))
+///     .push(Item::Gap(1))
+///     .push(Item::Flex(&"a.txt", 1));
+/// assert_eq!(row.render(24), "copying █████      a.txt");
+/// ```
+#[derive(Clone, Default)]
+pub struct Row<'a> {
+    items: Vec<Item<'a>>,
+}
+
+impl<'a> Row<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `item`
+    pub fn push(mut self, item: Item<'a>) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Render the row at `width` cells
+    ///
+    /// Fixed items that don't fit are truncated along with everything after them.
+    pub fn render(&self, width: usize) -> String {
+        let fixed = self
+            .items
+            .iter()
+            .map(|x| match *x {
+                Item::Fixed(widget) => Some(widget.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let used = self
+            .items
+            .iter()
+            .zip(&fixed)
+            .map(|(x, s)| match (*x, s) {
+                (Item::Gap(n), _) => n,
+                (_, Some(s)) => width::str_width(s),
+                _ => 0,
+            })
+            .sum::<usize>();
+        let total = self
+            .items
+            .iter()
+            .map(|x| match *x {
+                Item::Flex(_, weight) => u64::from(weight),
+                _ => 0,
+            })
+            .sum::<u64>();
+        let remaining = width.saturating_sub(used) as u64;
+        // Shares are computed from cumulative weight so that rounding never loses or gains a cell
+        let (mut weight, mut allocated) = (0, 0);
+        let mut line = String::new();
+        for (item, s) in self.items.iter().zip(&fixed) {
+            match *item {
+                Item::Fixed(_) => line.push_str(s.as_ref().unwrap()),
+                Item::Flex(widget, w) => {
+                    weight += u64::from(w);
+                    let end = if total == 0 {
+                        0
+                    } else {
+                        (remaining * weight).div_ceil(total)
+                    };
+                    line.push_str(&fit(widget, (end - allocated) as usize));
+                    allocated = end;
+                }
+                Item::Gap(n) => line.extend((0..n).map(|_| ' ')),
+            }
+        }
+        width::truncate(&line, width).into()
+    }
+
+    /// Display the row at `width` cells
+    pub fn display(&self, width: usize) -> impl Display + '_ {
+        RenderedRow { row: self, width }
+    }
+}
+
+struct RenderedRow<'a> {
+    row: &'a Row<'a>,
+    width: usize,
+}
+
+impl<'a> Display for RenderedRow<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.row.render(self.width))
+    }
+}
+
+/// Text limited to a number of terminal cells, with elided content replaced by `…`
+///
+/// Text is cut only between grapheme clusters, so combining marks and emoji sequences are never split. A formatter
+/// width pads the result.
+///
+/// # Examples
+/// ```
+/// # use yapb::layout::{Truncate, Elide};
+/// let path = "/home/user/projects/yapb/src/lib.rs";
+/// assert_eq!(Truncate(path, 16, Elide::End).to_string(), "/home/user/proj…");
+/// assert_eq!(Truncate(path, 16, Elide::Start).to_string(), "…yapb/src/lib.rs");
+/// assert_eq!(Truncate(path, 16, Elide::Middle).to_string(), "/home/us…/lib.rs");
+/// assert_eq!(format!("[{:8}]", Truncate("short", 16, Elide::End)), "[short   ]");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Truncate<'a>(pub &'a str, pub usize, pub Elide);
+
+/// Which part of the text a `Truncate` removes when it doesn't fit
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum Elide {
+    /// Keep the end, e.g. the file name of a path
+    Start,
+    /// Keep both ends
+    Middle,
+    /// Keep the beginning
+    #[default]
+    End,
+}
+
+impl<'a> Truncate<'a> {
+    /// The text kept before and after the ellipsis, or `None` if the text fits whole
+    ///
+    /// Both parts are empty if there's no room even for the ellipsis.
+    fn kept(&self) -> Option<(&'a str, &'a str)> {
+        let Truncate(text, cells, elide) = *self;
+        if width::str_width(text) <= cells {
+            return None;
+        }
+        // Leave room for the ellipsis
+        let budget = match cells.checked_sub(1) {
+            Some(x) => x,
+            None => return Some(("", "")),
+        };
+        let (head, tail) = match elide {
+            Elide::Start => (0, budget),
+            Elide::Middle => (budget.div_ceil(2), budget / 2),
+            Elide::End => (budget, 0),
+        };
+        let head = prefix_len(width::clusters(text), head);
+        let tail = prefix_len(
+            width::clusters(text).collect::<Vec<_>>().into_iter().rev(),
+            tail,
+        );
+        Some((&text[..head], &text[text.len() - tail..]))
+    }
+}
+
+impl<'a> Display for Truncate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (head, tail) = match self.kept() {
+            None => return width::pad(f, self.0),
+            Some(_) if self.1 == 0 => return width::pad(f, ""),
+            Some(x) => x,
+        };
+        let mut out = String::with_capacity(head.len() + tail.len() + 3);
+        out.push_str(head);
+        out.push('…');
+        out.push_str(tail);
+        width::pad(f, &out)
+    }
+}
+
+impl<'a> WidthHint for Truncate<'a> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        let natural = match self.kept() {
+            None => width::str_width(self.0),
+            Some(_) if self.1 == 0 => 0,
+            Some((head, tail)) => width::str_width(head) + 1 + width::str_width(tail),
+        };
+        natural.max(width.unwrap_or(0))
+    }
+}
+
+/// Length in bytes of the longest run of `clusters` fitting within `cells`
+fn prefix_len<'a>(clusters: impl Iterator<Item = (&'a str, usize)>, cells: usize) -> usize {
+    let mut used = 0;
+    let mut len = 0;
+    for (cluster, width) in clusters {
+        used += width;
+        if used > cells {
+            break;
+        }
+        len += cluster.len();
+    }
+    len
+}
+
+/// A widget surrounded by fixed text, with the formatter's width shared between them
+///
+/// The inner widget is formatted at the requested width minus the cells occupied by the border. If the result is
+/// narrower than requested, e.g. because the inner widget has a natural width, it's padded according to the
+/// formatter's fill and alignment. `Bordered` is the equivalent for a `Bar`, which it fills out to the border.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::layout::Decorated;
+/// let mut bar = Decorated::new("[", Bar::new(), "]");
+/// bar.set(0.5);
+/// assert_eq!(format!("{:10}", bar), "[████    ]");
+/// assert_eq!(format!("{:>5}", Decorated::with_border(Line::new(), Border::BRACKETS)), "  [|]");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decorated<T> {
+    border: Border,
+    inner: T,
+}
+
+impl<T> Decorated<T> {
+    pub fn new(prefix: &'static str, inner: T, suffix: &'static str) -> Self {
+        Self::with_border(
+            inner,
+            Border {
+                open: prefix,
+                close: suffix,
+            },
+        )
+    }
+
+    pub fn with_border(inner: T, border: Border) -> Self {
+        Self { border, inner }
+    }
+
+    pub fn border(&self) -> &Border {
+        &self.border
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Default> Default for Decorated<T> {
+    fn default() -> Self {
+        Self::new("", T::default(), "")
+    }
+}
+
+impl<T: Progress> Progress for Decorated<T> {
+    fn set(&mut self, value: f32) {
+        self.inner.set(value);
+    }
+}
+
+impl<T: Display> Display for Decorated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = match f.width() {
+            Some(width) => format!(
+                "{:1$}",
+                self.inner,
+                width.saturating_sub(self.border.width())
+            ),
+            None => self.inner.to_string(),
+        };
+        width::pad(
+            f,
+            &format!("{}{}{}", self.border.open, inner, self.border.close),
+        )
+    }
+}
+
+impl<T: WidthHint> WidthHint for Decorated<T> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        let decoration = self.border.width();
+        let hint = decoration
+            + self
+                .inner
+                .width_hint(width.map(|x| x.saturating_sub(decoration)));
+        hint.max(width.unwrap_or(0))
+    }
+}
+
+/// A widget with a label on the line above it, truncated to the widget's width
+///
+/// Rendering produces two lines separated by `\n`, the label first. The formatter's width applies to the widget,
+/// and the label is cut short with `…` rather than wrapping when it's wider.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::layout::Labeled;
+/// let mut bar = Labeled::new("downloading yapb-0.3.2.crate", Bar::new());
+/// bar.set(0.5);
+/// assert_eq!(format!("{:12}", bar), "downloading…\n██████      ");
+/// bar.set_label("done");
+/// assert_eq!(format!("{:4}", bar), "done\n██  ");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Labeled<T> {
+    label: String,
+    inner: T,
+}
+
+impl<T> Labeled<T> {
+    pub fn new(label: &str, inner: T) -> Self {
+        Self {
+            label: label.into(),
+            inner,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Replace the label, e.g. to show the item currently being processed
+    pub fn set_label(&mut self, label: &str) {
+        self.label.clear();
+        self.label.push_str(label);
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Progress> Progress for Labeled<T> {
+    fn set(&mut self, value: f32) {
+        self.inner.set(value);
+    }
+}
+
+impl<T: Display> Display for Labeled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = match f.width() {
+            Some(width) => format!("{:1$}", self.inner, width),
+            None => self.inner.to_string(),
+        };
+        let cells = width::str_width(&inner);
+        writeln!(f, "{}", Truncate(&self.label, cells, Elide::End))?;
+        f.write_str(&inner)
+    }
+}
+
+impl<T: WidthHint> WidthHint for Labeled<T> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.inner.width_hint(width)
+    }
+}
+
+/// Several lines of widgets whose columns are padded to a common width
+///
+/// Each column is as wide as its widest entry, unless given a fixed width, which suits widgets like `Bar` that have no
+/// natural width. Rows with fewer entries than others leave their trailing columns empty.
+///
+/// # Examples
+/// ```
+/// # use std::fmt::Alignment;
+/// # use yapb::*;
+/// # use yapb::layout::Columns;
+/// let (mut a, mut b) = (Bar::new(), Bar::new());
+/// a.set(0.5);
+/// b.set(1.0);
+/// let mut table = Columns::new();
+/// table.width(1, 4).align(2, Alignment::Right);
+/// table.push(&[&"download", &a, &"5%"]);
+/// table.push(&[&"install", &b, &"100%"]);
+/// assert_eq!(table.render(), "download ██     5%\ninstall  ████ 100%");
+/// ```
+#[derive(Clone, Default)]
+pub struct Columns<'a> {
+    rows: Vec<Vec<&'a dyn Display>>,
+    columns: Vec<Column>,
+}
+
+#[derive(Copy, Clone, Default)]
+struct Column {
+    align: Option<Alignment>,
+    width: Option<usize>,
+}
+
+impl<'a> Columns<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the alignment of column `index`, which is left-aligned by default
+    pub fn align(&mut self, index: usize, align: Alignment) -> &mut Self {
+        self.column(index).align = Some(align);
+        self
+    }
+
+    /// Format column `index` at exactly `width` cells rather than its widest entry's natural width
+    pub fn width(&mut self, index: usize, width: usize) -> &mut Self {
+        self.column(index).width = Some(width);
+        self
+    }
+
+    fn column(&mut self, index: usize) -> &mut Column {
+        if self.columns.len() <= index {
+            self.columns.resize(index + 1, Column::default());
+        }
+        &mut self.columns[index]
+    }
+
+    /// Append a row
+    pub fn push(&mut self, row: &[&'a dyn Display]) -> &mut Self {
+        self.rows.push(row.to_vec());
+        self
+    }
+
+    /// Render each row as a line, with columns separated by a space
+    pub fn lines(&self) -> Vec<String> {
+        let cells = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(
+                        |(i, widget)| match self.columns.get(i).and_then(|x| x.width) {
+                            Some(width) => fit(*widget, width),
+                            None => widget.to_string(),
+                        },
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let count = cells.iter().map(|x| x.len()).max().unwrap_or(0);
+        let widths = (0..count)
+            .map(|i| {
+                cells
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|x| width::str_width(x))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+        cells
+            .iter()
+            .map(|row| {
+                let mut line = String::new();
+                for (i, &width) in widths.iter().enumerate() {
+                    if i != 0 {
+                        line.push(' ');
+                    }
+                    let cell = row.get(i).map_or("", |x| &x[..]);
+                    let padding = width - width::str_width(cell);
+                    let before = match self.columns.get(i).and_then(|x| x.align) {
+                        Some(Alignment::Right) => padding,
+                        Some(Alignment::Center) => padding / 2,
+                        _ => 0,
+                    };
+                    line.extend((0..before).map(|_| ' '));
+                    line.push_str(cell);
+                    line.extend((before..padding).map(|_| ' '));
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Render all rows, separated by newlines
+    pub fn render(&self) -> String {
+        self.lines().join("\n")
+    }
+}
+
+/// Format `widget` at exactly `cells` terminal cells, truncating or padding with spaces as necessary
+fn fit(widget: &dyn Display, cells: usize) -> String {
+    let s = format!("{:1$}", widget, cells);
+    let mut s = String::from(width::truncate(&s, cells));
+    // `str`'s `Display` pads by `char`s rather than cells
+    for _ in width::str_width(&s)..cells {
+        s.push(' ');
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget() {
+        let line = StatusLine::new()
+            .fixed(&"long label", 4)
+            .text("|")
+            .flex(&"a")
+            .flex(&"b")
+            .fixed(&"進捗", 3);
+        assert_eq!(line.render(12), "long|a b 進 ");
+        assert_eq!(line.render(4), "long");
+    }
+
+    #[test]
+    fn weights() {
+        let row = Row::new()
+            .push(Item::Flex(&"a", 1))
+            .push(Item::Gap(1))
+            .push(Item::Flex(&"b", 2))
+            .push(Item::Flex(&"c", 0))
+            .push(Item::Fixed(&"|"));
+        assert_eq!(row.render(9), "a   b   |");
+        assert_eq!(row.render(2), " |");
+        assert_eq!(Row::new().push(Item::Flex(&"a", 0)).render(3), "");
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(Truncate("進捗状況", 6, Elide::End).to_string(), "進捗…");
+        assert_eq!(Truncate("進捗状況", 5, Elide::Start).to_string(), "…状況");
+        assert_eq!(
+            Truncate("ae\u{301}iou", 4, Elide::Middle).to_string(),
+            "ae\u{301}…u"
+        );
+        assert_eq!(Truncate("abc", 1, Elide::Middle).to_string(), "…");
+        assert_eq!(Truncate("abc", 0, Elide::End).to_string(), "");
+        assert_eq!(Truncate("abc", 3, Elide::End).to_string(), "abc");
+    }
+
+    #[test]
+    fn decorated() {
+        let label = Decorated::new("«", "abc", "»");
+        assert_eq!(format!("{:8}", label), "«abc   »");
+        assert_eq!(format!("{:1}", label), "«abc»");
+        assert_eq!(label.to_string(), "«abc»");
+        assert_eq!(
+            format!("{:-^7}", Decorated::new("«", ::Line::new(), "»")),
+            "--«|»--"
+        );
+        assert_eq!(format!("{:>3}", Decorated::<&str>::default()), "   ");
+    }
+
+    #[test]
+    fn labeled() {
+        let mut label = Labeled::new("進捗状況", Truncate("abc", 8, Elide::End));
+        assert_eq!(format!("{:6}", label), "進捗…\nabc   ");
+        assert_eq!(label.to_string(), "進…\nabc");
+        label.set_label("");
+        assert_eq!(label.to_string(), "\nabc");
+        assert_eq!(label.width_hint(Some(6)), 6);
+
+        /// Progress without a width hint
+        struct Percent(f32);
+        impl Display for Percent {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}%", self.0 * 100.0)
+            }
+        }
+        impl Progress for Percent {
+            fn set(&mut self, value: f32) {
+                self.0 = value;
+            }
+        }
+        let mut percent = Labeled::new("copying", Percent(0.0));
+        percent.set(0.5);
+        assert_eq!(percent.to_string(), "co…\n50%");
+    }
+
+    #[test]
+    fn columns() {
+        let mut table = Columns::new();
+        table.align(1, Alignment::Center);
+        table.push(&[&"進捗", &"a"]);
+        table.push(&[&"b", &"ccc", &1]);
+        assert_eq!(table.lines(), ["進捗  a   ", "b    ccc 1"]);
+        assert_eq!(Columns::new().render(), "");
+    }
+
+    #[test]
+    fn width_hints() {
+        let texts = [
+            Truncate("hello", 3, Elide::End),
+            Truncate("文字", 8, Elide::Start),
+            Truncate("進捗状況", 6, Elide::End),
+            Truncate("進捗状況", 4, Elide::Middle),
+            Truncate("abc", 0, Elide::End),
+        ];
+        for text in texts {
+            assert_eq!(width::str_width(&text.to_string()), text.width_hint(None));
+            assert_eq!(
+                width::str_width(&format!("{:4}", text)),
+                text.width_hint(Some(4))
+            );
+            assert_eq!(
+                width::str_width(&format!("{:6}", text)),
+                text.width_hint(Some(6))
+            );
+        }
+        let decorated = Decorated::new("«", Truncate("abc", 8, Elide::End), "»");
+        assert_eq!(decorated.width_hint(None), 5);
+        assert_eq!(
+            width::str_width(&format!("{:10}", decorated)),
+            decorated.width_hint(Some(10))
+        );
+    }
+}
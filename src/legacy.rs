@@ -0,0 +1,162 @@
+//! Graphics from the Symbols for Legacy Computing block added in Unicode 13
+//!
+//! Sextants divide each cell into a 2×3 grid, giving three times the vertical resolution of text for charts and
+//! multi-track bars. Horizontal resolution is unchanged, since `BarStyle::BLOCKS` already fills in eighths. Few fonts
+//! cover this block yet, so nothing here is used unless chosen explicitly; check that the output device supports
+//! `styles::Charset::Legacy` first.
+
+use std::fmt::{self, Display, Write};
+
+/// The sextant character with the sub-cells in `bits` filled
+///
+/// Bit 0 is the top left sub-cell, bit 1 the top right, and so on down to bit 5 at the bottom right. Patterns that
+/// already had characters before Unicode 13, namely the empty cell and full and half blocks, map to those.
+///
+/// # Examples
+/// ```
+/// # use yapb::legacy::sextant;
+/// assert_eq!(sextant(0b000001), '🬀');
+/// assert_eq!(sextant(0b010101), '▌');
+/// assert_eq!(sextant(0b111110), '🬻');
+/// ```
+pub fn sextant(bits: u8) -> char {
+    let bits = u32::from(bits & 0b111111);
+    match bits {
+        0 => ' ',
+        0b010101 => '▌',
+        0b101010 => '▐',
+        0b111111 => '█',
+        _ => {
+            // The block skips the patterns covered above
+            let skipped = u32::from(bits > 0b010101) + u32::from(bits > 0b101010);
+            ::std::char::from_u32(0x1FB00 + bits - 1 - skipped).unwrap()
+        }
+    }
+}
+
+/// A grid of sub-cell pixels drawn with sextants, two wide and three high per cell
+///
+/// # Examples
+/// ```
+/// # use yapb::legacy::Canvas;
+/// let mut canvas = Canvas::new(4, 1);
+/// canvas.hbar(0, 1.0);
+/// canvas.hbar(1, 0.5);
+/// canvas.hbar(2, 0.25);
+/// assert_eq!(canvas.to_string(), "█🬎🬂🬂");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Canvas {
+    columns: usize,
+    rows: usize,
+    /// Sextant bits of each cell, row-major
+    cells: Vec<u8>,
+}
+
+impl Canvas {
+    /// An empty canvas `columns` cells wide and `rows` lines high
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![0; columns * rows],
+        }
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> usize {
+        2 * self.columns
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> usize {
+        3 * self.rows
+    }
+
+    /// Whether the pixel at (`x`, `y`), measured from the top left, is filled
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (cell, bit) = self.locate(x, y);
+        self.cells[cell] & bit != 0
+    }
+
+    /// Fill or clear the pixel at (`x`, `y`), measured from the top left
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel lies outside the canvas.
+    pub fn set(&mut self, x: usize, y: usize, filled: bool) {
+        let (cell, bit) = self.locate(x, y);
+        if filled {
+            self.cells[cell] |= bit;
+        } else {
+            self.cells[cell] &= !bit;
+        }
+    }
+
+    /// Fill pixel row `y` from the left to `fraction` of the width, and clear the rest of it
+    pub fn hbar(&mut self, y: usize, fraction: f32) {
+        let width = self.width();
+        // NaN saturates to 0
+        let filled = (fraction.clamp(0.0, 1.0) * width as f32).round() as usize;
+        for x in 0..width {
+            self.set(x, y, x < filled);
+        }
+    }
+
+    /// Clear every pixel
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            *cell = 0;
+        }
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u8) {
+        assert!(x < self.width() && y < self.height(), "pixel out of bounds");
+        let cell = (y / 3) * self.columns + x / 2;
+        (cell, 1 << ((y % 3) * 2 + x % 2))
+    }
+}
+
+impl Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.columns.max(1)).enumerate() {
+            if i != 0 {
+                f.write_char('\n')?;
+            }
+            for &bits in row {
+                f.write_char(sextant(bits))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use width;
+
+    #[test]
+    fn sextants() {
+        let mut seen = (0..64).map(sextant).collect::<Vec<_>>();
+        assert_eq!(seen[0b111101], '🬺');
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 64);
+        for bits in 0..64 {
+            assert_eq!(width::char_width(sextant(bits)), 1);
+        }
+    }
+
+    #[test]
+    fn canvas() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set(0, 0, true);
+        canvas.set(3, 5, true);
+        assert!(canvas.get(3, 5));
+        assert!(!canvas.get(2, 5));
+        assert_eq!(canvas.to_string(), "🬀 \n 🬞");
+        canvas.clear();
+        assert_eq!(canvas.to_string(), "  \n  ");
+    }
+}
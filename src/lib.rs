@@ -15,23 +15,82 @@
 //! use yapb::{Bar, Progress};
 //!
 //! fn main() {
-//!   let mut bar = Bar::new();
+//!   let mut bar = Bar::new().framed("[", "]");
 //!   print!("{}", termion::cursor::Save);
 //!   for i in 0..100 {
 //!     bar.set(i as f32 / 100.0);
 //!     let (width, _) = termion::terminal_size().unwrap();
-//!     print!("{}{}[{:width$}]",
+//!     print!("{}{}{:width$}",
 //!            termion::clear::AfterCursor, termion::cursor::Restore,
-//!            bar, width = width as usize - 2);
+//!            bar, width = width as usize);
 //!     io::stdout().flush().unwrap();
 //!     thread::sleep(time::Duration::from_millis(100));
 //!   }
 //! }
 //! ```
 
+#[cfg(feature = "crossterm")]
+extern crate crossterm;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "futures")]
+extern crate futures_io;
+#[cfg(feature = "ratatui")]
+extern crate ratatui_core;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(feature = "termion")]
+extern crate termion;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+
+use std::error::Error;
 use std::fmt::{self, Display, Write};
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+pub mod diff;
+pub mod duration;
+#[cfg(feature = "egui")]
+pub mod egui;
+#[cfg(feature = "emoji")]
+pub mod emoji;
+pub mod estimate;
+pub mod html;
+#[cfg(feature = "io")]
+pub mod io;
+#[cfg(feature = "iter")]
+pub mod iter;
+pub mod layout;
+pub mod legacy;
+#[cfg(feature = "log")]
+pub mod log;
+pub mod multi;
+pub mod osc;
+pub mod plain;
 pub mod prefix;
+pub mod presets;
+#[cfg(feature = "ratatui")]
+pub mod ratatui;
+pub mod record;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod report;
+#[cfg(feature = "sixel")]
+pub mod sixel;
+pub mod snapshot;
+pub mod stats;
+pub mod styles;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod template;
+pub mod theme;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+mod width;
 
 /// Indicators that communicate a proportion of progress towards a known end point
 pub trait Progress: Display {
@@ -42,6 +101,24 @@ pub trait Progress: Display {
     fn set(&mut self, value: f32);
 }
 
+/// Widgets that can report the number of terminal cells they occupy without being rendered
+///
+/// This allows layout code to budget space for a widget without formatting it into a scratch string and measuring the
+/// result.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// assert_eq!(Bar::new().width_hint(Some(10)), 10);
+/// assert_eq!(Bar::new().width_hint(None), 80);
+/// assert_eq!(Spinner4::new().width_hint(Some(10)), 1);
+/// ```
+pub trait WidthHint {
+    /// Number of cells occupied when formatted with `width`, as in `format!("{:width$}", x)`, or with no width if
+    /// `None`
+    fn width_hint(&self, width: Option<usize>) -> usize;
+}
+
 /// An unusually high-resolution progress bar using Unicode block elements
 ///
 /// # Examples
@@ -51,69 +128,726 @@ pub trait Progress: Display {
 /// bar.set(0.55);
 /// assert_eq!(format!("[{:10}]", bar), "[█████▌    ]");
 /// ```
-#[derive(Debug, Copy, Clone)]
+///
+/// Other character sets can be selected with `with_style`, e.g. for devices lacking Unicode support:
+/// ```
+/// # use yapb::*;
+/// let mut bar = Bar::with_style(BarStyle::CP437);
+/// bar.set(0.55);
+/// assert_eq!(format!("[{:10}]", bar), "[█████▒    ]");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone, Default)]
 pub struct Bar {
-    progress: f32,
+    /// Progress in units of 1/65536, in [0, 65536]
+    progress: u32,
+    style: BarStyle,
 }
 
+/// Fixed-point scale of `Bar`'s internal state
+const BAR_ONE: u32 = 1 << 16;
+
 impl Bar {
     pub fn new() -> Self {
-        Bar { progress: 0.0 }
+        Self::with_style(BarStyle::BLOCKS)
+    }
+
+    pub fn with_style(style: BarStyle) -> Self {
+        Bar { progress: 0, style }
     }
 
     pub fn get(&self) -> f32 {
-        self.progress
+        self.progress as f32 / BAR_ONE as f32
+    }
+
+    pub fn style(&self) -> &BarStyle {
+        &self.style
+    }
+
+    /// Draw the bar between `open` and `close`, counting them towards its width
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// let mut bar = Bar::new().framed("[", "]");
+    /// bar.set(0.5);
+    /// assert_eq!(format!("{:10}", bar), "[████    ]");
+    /// ```
+    pub fn framed(self, open: &'static str, close: &'static str) -> Bordered {
+        Bordered::new(self, Border { open, close })
+    }
+
+    /// Number of full glyphs, the partially filled glyph following them if any, the number of empty glyphs, and the
+    /// number of cells left over, when drawn `width` cells wide
+    pub(crate) fn segments(&self, width: u32) -> (u32, Option<&'static str>, u32, u32) {
+        let style = &self.style;
+        let cell = style.glyph_width();
+        let slots = width / cell;
+        // Number of distinct levels of fill each glyph can display
+        let levels = style.partial.len() as u64 + 1;
+        // Scale by width in units of partial glyphs, rounding down, in exact integer arithmetic
+        let units = u64::from(slots) * levels * u64::from(self.progress) / u64::from(BAR_ONE);
+        let full = (units / levels) as u32;
+        let head = match (units % levels) as usize {
+            0 => None,
+            n => Some(style.partial[n - 1]),
+        };
+        let empty = slots - full - head.map_or(0, |_| 1);
+        (full, head, empty, width % cell)
     }
 }
 
 impl Progress for Bar {
     fn set(&mut self, value: f32) {
-        self.progress = value;
+        // NaN saturates to 0
+        self.progress = (value.clamp(0.0, 1.0) * BAR_ONE as f32).round() as u32;
     }
 }
 
 impl Display for Bar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let width = f.width().unwrap_or(80) as u32;
-        // Scale by width, rounding to nearest
-        let count = width as f32 * self.progress.max(0.0).min(1.0);
-        let whole = count.trunc() as u32;
-        for _ in 0..whole {
-            f.write_char('█')?;
+        self.draw(f, width)
+    }
+}
+
+impl Bar {
+    /// Write the bar `width` cells wide, padding with the formatter's fill
+    fn draw(&self, f: &mut fmt::Formatter, width: u32) -> fmt::Result {
+        let (full, head, empty, pad) = self.segments(width);
+        for _ in 0..full {
+            f.write_str(self.style.full)?;
         }
-        let fraction = (count.fract() * 8.0).trunc() as u32;
-        let fill = f.fill();
-        if whole < width {
-            f.write_char(match fraction {
-                0 => fill,
-                1 => '▏',
-                2 => '▎',
-                3 => '▍',
-                4 => '▌',
-                5 => '▋',
-                6 => '▊',
-                7 => '▉',
-                _ => unreachable!(),
-            })?;
-            for _ in whole..(width - 1) {
-                f.write_char(fill)?;
+        if let Some(head) = head {
+            f.write_str(head)?;
+        }
+        self.style.write_empty(f, empty)?;
+        write_fill(f, pad)
+    }
+}
+
+/// Write `count` copies of the formatter's fill character
+fn write_fill(f: &mut fmt::Formatter, count: u32) -> fmt::Result {
+    let fill = f.fill();
+    for _ in 0..count {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+impl WidthHint for Bar {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(80)
+    }
+}
+
+/// Glyphs used to draw a `Bar`
+///
+/// Each glyph is a string rather than a `char` so that it may be a grapheme cluster of several code points, e.g. an
+/// emoji with a variation selector. Every glyph must occupy the same number of terminal cells as `full`. The bar is
+/// drawn with as many glyphs as fit in its width, and any cells left over are padded with the formatter's fill.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Bar::with_style(BarStyle {
+///     full: "🟩",
+///     partial: &[],
+///     empty: Some("⬜"),
+/// });
+/// bar.set(0.5);
+/// assert_eq!(format!("[{:9}]", bar), "[🟩🟩⬜⬜ ]");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub struct BarStyle {
+    /// Drawn for each completely filled glyph
+    pub full: &'static str,
+    /// Drawn for a partially filled glyph, in increasing order of fill
+    ///
+    /// Longer sequences give finer resolution. If empty, the bar advances in whole glyphs.
+    pub partial: &'static [&'static str],
+    /// Drawn for each empty glyph, or `None` to use the formatter's fill character for each cell
+    pub empty: Option<&'static str>,
+}
+
+impl BarStyle {
+    /// Unicode block elements giving eighth-cell resolution
+    pub const BLOCKS: BarStyle = BarStyle {
+        full: "█",
+        partial: &["▏", "▎", "▍", "▌", "▋", "▊", "▉"],
+        empty: None,
+    };
+
+    /// Shades available in code page 437, for legacy Windows consoles and BIOS-style serial terminals
+    ///
+    /// See `styles::compatible(Charset::Cp437)` for spinners usable in the same environments.
+    pub const CP437: BarStyle = BarStyle {
+        full: "█",
+        partial: &["░", "▒", "▓"],
+        empty: None,
+    };
+
+    /// Plain 7-bit ASCII
+    pub const ASCII: BarStyle = BarStyle {
+        full: "#",
+        partial: &["-"],
+        empty: None,
+    };
+
+    /// Full-width forms two cells wide, for aligning with CJK text
+    ///
+    /// An odd width leaves a single cell of fill at the end.
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// let mut bar = Bar::with_style(BarStyle::FULLWIDTH);
+    /// bar.set(0.5);
+    /// assert_eq!(format!("[{:10}]", bar), "[＝＝－    ]");
+    /// assert_eq!(format!("[{:9}]", bar), "[＝＝     ]");
+    /// ```
+    pub const FULLWIDTH: BarStyle = BarStyle {
+        full: "＝",
+        partial: &["－"],
+        empty: None,
+    };
+
+    /// Number of terminal cells occupied by each glyph, at least 1
+    pub fn glyph_width(&self) -> u32 {
+        (width::str_width(self.full) as u32).max(1)
+    }
+
+    /// Write `count` empty glyphs
+    pub(crate) fn write_empty(&self, f: &mut fmt::Formatter, count: u32) -> fmt::Result {
+        match self.empty {
+            Some(empty) => {
+                for _ in 0..count {
+                    f.write_str(empty)?;
+                }
+                Ok(())
             }
+            None => write_fill(f, count * self.glyph_width()),
+        }
+    }
+}
+
+impl Default for BarStyle {
+    fn default() -> Self {
+        BarStyle::BLOCKS
+    }
+}
+
+/// A `Bar` drawn between end caps, which count towards its width
+///
+/// The formatter's width covers the whole widget, so the bar can be sized to the terminal directly rather than
+/// subtracting the caps by hand. `layout::Decorated` draws a `Border` around other widgets.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Bordered::new(Bar::new(), Border::HEAVY);
+/// bar.set(0.55);
+/// assert_eq!(format!("{:12}", bar), "┃█████▌    ┃");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone, Default)]
+pub struct Bordered {
+    bar: Bar,
+    border: Border,
+}
+
+impl Bordered {
+    pub fn new(bar: Bar, border: Border) -> Self {
+        Self { bar, border }
+    }
+
+    pub fn get(&self) -> f32 {
+        self.bar.get()
+    }
+
+    pub fn border(&self) -> &Border {
+        &self.border
+    }
+
+    pub fn get_ref(&self) -> &Bar {
+        &self.bar
+    }
+
+    pub fn get_mut(&mut self) -> &mut Bar {
+        &mut self.bar
+    }
+
+    pub fn into_inner(self) -> Bar {
+        self.bar
+    }
+}
+
+impl Progress for Bordered {
+    fn set(&mut self, value: f32) {
+        self.bar.set(value);
+    }
+}
+
+impl Display for Bordered {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().unwrap_or(80) as u32;
+        f.write_str(self.border.open)?;
+        self.bar
+            .draw(f, width.saturating_sub(self.border.width() as u32))?;
+        f.write_str(self.border.close)
+    }
+}
+
+impl WidthHint for Bordered {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(80).max(self.border.width())
+    }
+}
+
+/// End caps drawn around a `Bordered` bar
+///
+/// Caps that don't fit in the requested width are drawn regardless, leaving no room for the bar.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub struct Border {
+    /// Drawn before the bar
+    pub open: &'static str,
+    /// Drawn after the bar
+    pub close: &'static str,
+}
+
+impl Border {
+    /// Heavy vertical lines, matching the height of block elements
+    pub const HEAVY: Border = Border {
+        open: "┃",
+        close: "┃",
+    };
+
+    /// Light vertical lines
+    pub const LIGHT: Border = Border {
+        open: "│",
+        close: "│",
+    };
+
+    /// Light half-lines, for a bar that sits inline with its surroundings
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// let mut bar = Bordered::new(Bar::new(), Border::ROUNDED);
+    /// bar.set(0.5);
+    /// assert_eq!(format!("{:6}", bar), "╴██  ╶");
+    /// ```
+    pub const ROUNDED: Border = Border {
+        open: "╴",
+        close: "╶",
+    };
+
+    /// Square brackets, for devices lacking box drawing characters
+    pub const BRACKETS: Border = Border {
+        open: "[",
+        close: "]",
+    };
+
+    /// Number of terminal cells occupied by both caps together
+    pub fn width(&self) -> usize {
+        width::str_width(self.open) + width::str_width(self.close)
+    }
+}
+
+impl Default for Border {
+    fn default() -> Self {
+        Border::HEAVY
+    }
+}
+
+/// Adapter that eases the displayed value of a `Progress` indicator towards the most recently set value
+///
+/// Large jumps in progress are animated over successive frames rather than appearing instantaneously. `set` records the
+/// target, and `tick` should be called once per frame to advance the animation.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Animator::new(Bar::new(), Easing::Linear(0.25));
+/// bar.set(1.0);
+/// assert_eq!(format!("{:4}", bar), "    ");
+/// bar.tick();
+/// assert_eq!(format!("{:4}", bar), "█   ");
+/// bar.tick();
+/// assert_eq!(format!("{:4}", bar), "██  ");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Animator<P> {
+    inner: P,
+    easing: Easing,
+    target: f32,
+    current: f32,
+}
+
+/// How an `Animator` approaches its target
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    /// Move at a constant speed of at most this much per frame
+    Linear(f32),
+    /// Cover this fraction, in (0, 1], of the remaining distance each frame, slowing as the target is approached
+    Exponential(f32),
+}
+
+impl<P: Progress> Animator<P> {
+    /// Wrap `inner`, which should be displaying no progress
+    pub fn new(inner: P, easing: Easing) -> Self {
+        Self {
+            inner,
+            easing,
+            target: 0.0,
+            current: 0.0,
         }
-        Ok(())
+    }
+
+    /// Advance the animation by one frame, returning whether the target has yet to be reached
+    pub fn tick(&mut self) -> bool {
+        let distance = self.target - self.current;
+        self.current = match self.easing {
+            Easing::Linear(speed) if distance.abs() <= speed => self.target,
+            Easing::Linear(speed) => self.current + speed.copysign(distance),
+            // Snap once the difference is too small to be visible
+            Easing::Exponential(_) if distance.abs() < 1e-4 => self.target,
+            Easing::Exponential(factor) => self.current + distance * factor,
+        };
+        self.inner.set(self.current);
+        self.current != self.target
+    }
+
+    /// Immediately display the target value
+    pub fn finish(&mut self) {
+        self.current = self.target;
+        self.inner.set(self.current);
+    }
+
+    /// The value currently being displayed
+    pub fn displayed(&self) -> f32 {
+        self.current
+    }
+
+    /// The value being approached
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn get_ref(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: Progress> Progress for Animator<P> {
+    fn set(&mut self, value: f32) {
+        self.target = value;
+    }
+}
+
+impl<P: Display> Display for Animator<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<P: WidthHint> WidthHint for Animator<P> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.inner.width_hint(width)
+    }
+}
+
+/// Adapter that displays the weighted combination of several independently reported stages of progress
+///
+/// Each child is identified by a key and contributes in proportion to its weight, so e.g. a download weighted 7 and an
+/// install weighted 3 yield 70% overall progress once the download is complete.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bar = Aggregate::new(Bar::new());
+/// bar.add("download", 7.0);
+/// bar.add("extract", 2.0);
+/// bar.add("install", 1.0);
+/// bar.update(&"download", 1.0);
+/// bar.update(&"extract", 0.5);
+/// assert_eq!(bar.get(), 0.8);
+/// assert_eq!(format!("{:10}", bar), "████████  ");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Aggregate<K, P> {
+    inner: P,
+    children: Vec<Child<K>>,
+}
+
+#[derive(Debug, Clone)]
+struct Child<K> {
+    key: K,
+    weight: f32,
+    value: f32,
+}
+
+impl<K: PartialEq, P: Progress> Aggregate<K, P> {
+    /// Wrap `inner`, which should be displaying no progress
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            children: Vec::new(),
+        }
+    }
+
+    /// Add a child with no progress, or change the weight of an existing one
+    ///
+    /// `weight` must be non-negative. Only the ratios between weights matter.
+    pub fn add(&mut self, key: K, weight: f32) {
+        match self.children.iter_mut().find(|x| x.key == key) {
+            Some(child) => child.weight = weight,
+            None => self.children.push(Child {
+                key,
+                weight,
+                value: 0.0,
+            }),
+        }
+        self.refresh();
+    }
+
+    /// Remove a child, returning its progress if it was present
+    pub fn remove(&mut self, key: &K) -> Option<f32> {
+        let i = self.children.iter().position(|x| x.key == *key)?;
+        let child = self.children.remove(i);
+        self.refresh();
+        Some(child.value)
+    }
+
+    /// Set the progress of a child, returning whether it was present
+    ///
+    /// `value` must be in [0, 1].
+    pub fn update(&mut self, key: &K, value: f32) -> bool {
+        match self.children.iter_mut().find(|x| x.key == *key) {
+            Some(child) => child.value = value.clamp(0.0, 1.0),
+            None => return false,
+        }
+        self.refresh();
+        true
+    }
+
+    /// The progress of a child
+    pub fn child(&self, key: &K) -> Option<f32> {
+        self.children
+            .iter()
+            .find(|x| x.key == *key)
+            .map(|x| x.value)
+    }
+
+    /// The overall progress, or 0 if there are no children with nonzero weight
+    pub fn get(&self) -> f32 {
+        let total = self.children.iter().map(|x| x.weight).sum::<f32>();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let done = self
+            .children
+            .iter()
+            .map(|x| x.weight * x.value)
+            .sum::<f32>();
+        (done / total).min(1.0)
+    }
+
+    pub fn get_ref(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    fn refresh(&mut self) {
+        let value = self.get();
+        self.inner.set(value);
+    }
+}
+
+/// Sets every child's progress to `value`, e.g. 1.0 to mark everything complete
+impl<K: PartialEq, P: Progress> Progress for Aggregate<K, P> {
+    fn set(&mut self, value: f32) {
+        let value = value.clamp(0.0, 1.0);
+        for child in &mut self.children {
+            child.value = value;
+        }
+        self.refresh();
+    }
+}
+
+impl<K, P: Display> Display for Aggregate<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<K, P: WidthHint> WidthHint for Aggregate<K, P> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.inner.width_hint(width)
     }
 }
 
 /// Indicators that animate through some number of states to indicate activity with indefinite duration
 ///
-/// Incrementing a state by 1 advances by one frame of animation. Implementations of these two setters should only be a
-/// handful of instructions, with all complexity deferred to the `Display` impl.
+/// Incrementing a state by 1 advances by one frame of animation. Implementations of `set`, `get`, `step`, and `frames`
+/// should only be a handful of instructions, with all complexity deferred to the `Display` impl; `set_time` and
+/// `iter_frames` are provided in terms of them.
+///
+/// Built-in spinners also implement `AddAssign<u32>`, so `spinner += 1` is equivalent to `spinner.step(1)`.
 pub trait Spinner: Display {
     /// Set a specific state
     fn set(&mut self, value: u32);
+    /// Get the current state
+    ///
+    /// Passing the result to `set` restores the current frame, e.g. when recreating a spinner for a redraw.
+    fn get(&self) -> u32;
     /// Advance the current state `count` times.
     fn step(&mut self, count: u32);
+    /// Number of states in one full cycle of the animation
+    fn frames(&self) -> u32;
+
+    /// Set the state to that reached after animating for `elapsed` at `fps` frames per second
+    ///
+    /// Driving a spinner from a clock rather than by stepping once per redraw keeps the animation speed independent
+    /// of how often the caller's event loop happens to run.
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// # use std::time::Duration;
+    /// let mut spinner = Spinner4::new();
+    /// spinner.set_time(Duration::from_millis(2500), 2.0);
+    /// assert_eq!(format!("{}", spinner), "▘");
+    /// ```
+    fn set_time(&mut self, elapsed: Duration, fps: f32) {
+        let frame = (elapsed.as_secs_f64() * f64::from(fps)) as u64;
+        // Not reduced by `frames`, which for spinners like `Bounce` is only the cycle length at their default width.
+        // Wrapping matches `step`.
+        self.set(frame as u32);
+    }
+
+    /// Iterate over the rendering of each frame in one full cycle, starting from state 0
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// assert_eq!(Spinner4::new().iter_frames().collect::<String>(), "▖▘▝▗");
+    /// ```
+    fn iter_frames(&self) -> IterFrames<Self>
+    where
+        Self: Sized + Clone,
+    {
+        IterFrames {
+            spinner: self.clone(),
+            next: 0,
+        }
+    }
+}
+
+/// Iterator over the rendered frames of a `Spinner`, constructed by `Spinner::iter_frames`
+#[derive(Debug, Clone)]
+pub struct IterFrames<S> {
+    spinner: S,
+    next: u32,
+}
+
+impl<S: Spinner> Iterator for IterFrames<S> {
+    type Item = String;
+    fn next(&mut self) -> Option<String> {
+        if self.next >= self.spinner.frames() {
+            return None;
+        }
+        self.spinner.set(self.next);
+        self.next += 1;
+        Some(self.spinner.to_string())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.spinner.frames() - self.next) as usize;
+        (n, Some(n))
+    }
+}
+
+impl<S: Spinner> ExactSizeIterator for IterFrames<S> {}
+
+/// Implement `Add<u32>` and `AddAssign<u32>` in terms of `Spinner::step`
+///
+/// Coherence rules forbid a blanket impl of foreign operator traits over all `Spinner`s, so every spinner must be
+/// listed here instead.
+macro_rules! spinner_ops {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl AddAssign<u32> for $ty {
+                fn add_assign(&mut self, count: u32) {
+                    self.step(count);
+                }
+            }
+
+            impl Add<u32> for $ty {
+                type Output = Self;
+                fn add(mut self, count: u32) -> Self {
+                    self.step(count);
+                    self
+                }
+            }
+        )*
+    };
+}
+
+spinner_ops!(
+    Counter256,
+    Spinner8,
+    Counter16,
+    Spinner4,
+    Snake,
+    CustomSpinner<'_>,
+    Line,
+    Dots,
+    Dots2,
+    Dots3,
+    Arrow,
+    Bounce,
+    Scanner,
+    WideSnake,
+    Dissolve,
+    Wave,
+    HalfBlock,
+    Trail,
+    AnySpinner
+);
+
+#[cfg(feature = "emoji")]
+spinner_ops!(
+    emoji::Moon,
+    emoji::Clock,
+    emoji::Hourglass,
+    emoji::Globe,
+    emoji::Weather
+);
+
+/// Implement `WidthHint` for spinners that always draw exactly one cell, ignoring the requested width
+macro_rules! single_cell {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl WidthHint for $ty {
+                fn width_hint(&self, _: Option<usize>) -> usize {
+                    1
+                }
+            }
+        )*
+    };
 }
 
+single_cell!(
+    Counter256, Spinner8, Counter16, Spinner4, Snake, Line, Dots, Dots2, Dots3, Arrow, Dissolve,
+    HalfBlock, Trail,
+);
+
 /// A spinner that cycles through 256 states by counting in binary using braille
 ///
 /// # Examples
@@ -126,14 +860,36 @@ pub trait Spinner: Display {
 /// spinner.step(0xF0);
 /// assert_eq!(format!("{}", spinner), "⣿");
 /// ```
-#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
 pub struct Counter256 {
     state: u8,
+    gray: bool,
 }
 
 impl Counter256 {
     pub fn new() -> Self {
-        Self { state: 0 }
+        Self {
+            state: 0,
+            gray: false,
+        }
+    }
+
+    /// Construct a counter that counts in Gray code, so that exactly one dot changes on each step
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// let mut spinner = Counter256::gray();
+    /// spinner.step(2);
+    /// assert_eq!(format!("{}", spinner), "⠃");
+    /// spinner.step(1);
+    /// assert_eq!(format!("{}", spinner), "⠂");
+    /// ```
+    pub fn gray() -> Self {
+        Self {
+            state: 0,
+            gray: true,
+        }
     }
 }
 
@@ -141,9 +897,15 @@ impl Spinner for Counter256 {
     fn set(&mut self, state: u32) {
         self.state = state as u8;
     }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
     fn step(&mut self, count: u32) {
         self.state = self.state.wrapping_add(count as u8);
     }
+    fn frames(&self) -> u32 {
+        256
+    }
 }
 
 fn braille_binary(value: u8) -> char {
@@ -154,12 +916,17 @@ fn braille_binary(value: u8) -> char {
 
 impl Display for Counter256 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_char(braille_binary(self.state))
+        let value = if self.gray {
+            self.state ^ (self.state >> 1)
+        } else {
+            self.state
+        };
+        f.write_char(braille_binary(value))
     }
 }
 
 /// A spinner that cycles through 8 states with a single spinning braille dot
-#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
 pub struct Spinner8 {
     state: u8,
 }
@@ -176,9 +943,15 @@ impl Spinner for Spinner8 {
     fn set(&mut self, state: u32) {
         self.state = state as u8 % SPINNER8_STATES.len() as u8;
     }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
     fn step(&mut self, count: u32) {
         self.state = self.state.wrapping_add(count as u8) % SPINNER8_STATES.len() as u8;
     }
+    fn frames(&self) -> u32 {
+        SPINNER8_STATES.len() as u32
+    }
 }
 
 impl Display for Spinner8 {
@@ -188,7 +961,7 @@ impl Display for Spinner8 {
 }
 
 /// A spinner that cycles through 16 states by counting in binary using block elements
-#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
 pub struct Counter16 {
     state: u8,
 }
@@ -207,9 +980,15 @@ impl Spinner for Counter16 {
     fn set(&mut self, state: u32) {
         self.state = state as u8 % COUNTER16_STATES.len() as u8;
     }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
     fn step(&mut self, count: u32) {
         self.state = self.state.wrapping_add(count as u8) % COUNTER16_STATES.len() as u8;
     }
+    fn frames(&self) -> u32 {
+        COUNTER16_STATES.len() as u32
+    }
 }
 
 impl Display for Counter16 {
@@ -219,7 +998,7 @@ impl Display for Counter16 {
 }
 
 /// A spinner that cycles through 4 states with a single spinning block element
-#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
 pub struct Spinner4 {
     state: u8,
 }
@@ -236,9 +1015,15 @@ impl Spinner for Spinner4 {
     fn set(&mut self, state: u32) {
         self.state = state as u8 % SPINNER4_STATES.len() as u8;
     }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
     fn step(&mut self, count: u32) {
         self.state = self.state.wrapping_add(count as u8) % SPINNER4_STATES.len() as u8;
     }
+    fn frames(&self) -> u32 {
+        SPINNER4_STATES.len() as u32
+    }
 }
 
 impl Display for Spinner4 {
@@ -247,35 +1032,359 @@ impl Display for Spinner4 {
     }
 }
 
-/// A spinner that cycles through many states with a snake made of 1-6 braille dots
-#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
-pub struct Snake {
-    state: u32,
+/// A spinner that cycles through 4 states with a rotating ASCII line, for devices lacking Unicode support
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Line {
+    state: u8,
 }
 
-impl Snake {
+const LINE_STATES: [char; 4] = ['|', '/', '-', '\\'];
+
+impl Line {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Line {
+    fn set(&mut self, state: u32) {
+        self.state = state as u8 % LINE_STATES.len() as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count as u8) % LINE_STATES.len() as u8;
+    }
+    fn frames(&self) -> u32 {
+        LINE_STATES.len() as u32
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { LINE_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 10 states with a group of braille dots chasing around a cell
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Dots {
+    state: u8,
+}
+
+const DOTS_STATES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+impl Dots {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Dots {
+    fn set(&mut self, state: u32) {
+        self.state = (state % DOTS_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = DOTS_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        DOTS_STATES.len() as u32
+    }
+}
+
+impl Display for Dots {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { DOTS_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 8 states with a single gap circling a full braille cell
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Dots2 {
+    state: u8,
+}
+
+const DOTS2_STATES: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+
+impl Dots2 {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Dots2 {
+    fn set(&mut self, state: u32) {
+        self.state = state as u8 % DOTS2_STATES.len() as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count as u8) % DOTS2_STATES.len() as u8;
+    }
+    fn frames(&self) -> u32 {
+        DOTS2_STATES.len() as u32
+    }
+}
+
+impl Display for Dots2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { DOTS2_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 10 states with a short line of braille dots wriggling around a cell
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Dots3 {
+    state: u8,
+}
+
+const DOTS3_STATES: [char; 10] = ['⠋', '⠙', '⠚', '⠞', '⠖', '⠦', '⠴', '⠲', '⠳', '⠓'];
+
+impl Dots3 {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Dots3 {
+    fn set(&mut self, state: u32) {
+        self.state = (state % DOTS3_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = DOTS3_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        DOTS3_STATES.len() as u32
+    }
+}
+
+impl Display for Dots3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { DOTS3_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 8 states with an arrow rotating clockwise, legible even at small font sizes
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Arrow {
+    state: u8,
+}
+
+const ARROW_STATES: [char; 8] = ['←', '↖', '↑', '↗', '→', '↘', '↓', '↙'];
+
+impl Arrow {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Arrow {
+    fn set(&mut self, state: u32) {
+        self.state = (state % ARROW_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = ARROW_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        ARROW_STATES.len() as u32
+    }
+}
+
+impl Display for Arrow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { ARROW_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 4 states with a half-filled cell rotating clockwise, using only characters available
+/// in code page 437
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct HalfBlock {
+    state: u8,
+}
+
+const HALF_BLOCK_STATES: [char; 4] = ['▀', '▐', '▄', '▌'];
+
+impl HalfBlock {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for HalfBlock {
+    fn set(&mut self, state: u32) {
+        self.state = (state % HALF_BLOCK_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = HALF_BLOCK_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        HALF_BLOCK_STATES.len() as u32
+    }
+}
+
+impl Display for HalfBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { HALF_BLOCK_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through 16 states with a head circling the quadrants of a cell clockwise, leaving a trail that
+/// fades through decreasing shade densities
+///
+/// A cell holds one glyph, so the trail follows the head in time rather than sharing the cell with it: after the head
+/// is drawn in each quadrant, the cell fades through `▓▒░` before the head appears in the next quadrant. Faster ticks
+/// shorten the afterglow along with each revolution.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut trail = Trail::new();
+/// assert_eq!(format!("{}", trail), "▘");
+/// trail.step(1);
+/// assert_eq!(format!("{}", trail), "▓");
+/// trail.step(3);
+/// assert_eq!(format!("{}", trail), "▝");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Trail {
+    state: u8,
+}
+
+const TRAIL_STATES: [char; 16] = [
+    '▘', '▓', '▒', '░', '▝', '▓', '▒', '░', '▗', '▓', '▒', '░', '▖', '▓', '▒', '░',
+];
+
+impl Trail {
     pub fn new() -> Self {
         Self { state: 0 }
     }
 }
 
+impl Spinner for Trail {
+    fn set(&mut self, state: u32) {
+        self.state = (state % TRAIL_STATES.len() as u32) as u8;
+    }
+    fn get(&self) -> u32 {
+        u32::from(self.state)
+    }
+    fn step(&mut self, count: u32) {
+        let frames = TRAIL_STATES.len() as u32;
+        self.state = ((u32::from(self.state) + count % frames) % frames) as u8;
+    }
+    fn frames(&self) -> u32 {
+        TRAIL_STATES.len() as u32
+    }
+}
+
+impl Display for Trail {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char(*unsafe { TRAIL_STATES.get_unchecked(self.state as usize) })
+    }
+}
+
+/// A spinner that cycles through many states with a snake of braille dots circling the cell
+///
+/// The snake alternately stretches forward and contracts from behind, between 1 and 6 dots long by default.
+/// `with_params` allows the animation to be tuned, e.g. to choose other lengths or to suit a slower or faster tick
+/// rate.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+pub struct Snake {
+    state: u32,
+    min_length: u8,
+    max_length: u8,
+    period: u32,
+    advance: u8,
+}
+
+impl Snake {
+    pub fn new() -> Self {
+        Self::with_params(1, 6, 10, 5)
+    }
+
+    /// Construct a snake whose length varies between `min_length` and `max_length` dots over a wobble of `period`
+    /// frames, and whose tail advances `advance` dots around the cell during each wobble
+    ///
+    /// # Panics
+    /// If the lengths are not in [1, 8] with `min_length <= max_length`, or if `period` is odd or zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::*;
+    /// // A shorter snake that stretches and contracts more slowly and makes a quarter turn each wobble
+    /// let snake = Snake::with_params(1, 3, 8, 2);
+    /// assert_eq!(snake.frames(), 32);
+    /// ```
+    pub fn with_params(min_length: u8, max_length: u8, period: u32, advance: u8) -> Self {
+        assert!(
+            1 <= min_length && min_length <= max_length && max_length <= 8,
+            "snake lengths must be in [1, 8]"
+        );
+        assert!(
+            period != 0 && period & 1 == 0,
+            "snake period must be even and nonzero"
+        );
+        Self {
+            state: 0,
+            min_length,
+            max_length,
+            period,
+            advance: advance % 8,
+        }
+    }
+}
+
+impl Default for Snake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Spinner for Snake {
     fn set(&mut self, state: u32) {
         self.state = state;
     }
+    fn get(&self) -> u32 {
+        self.state
+    }
     fn step(&mut self, count: u32) {
         self.state = self.state.wrapping_add(count);
     }
+    fn frames(&self) -> u32 {
+        // The tail advances by `advance` of 8 dots per wobble, so returns to its origin after this many wobbles
+        self.period * (8 / gcd(u64::from(self.advance), 8) as u32)
+    }
 }
 
 impl Display for Snake {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        const WOBBLE: u32 = 5;
-        let length = (((self.state % (2 * WOBBLE)) as i32 - (WOBBLE as i32)).abs() + 1) as u32;
-        let bits = !(0xFFu8 << length);
-        let position = (WOBBLE * (self.state / (2 * WOBBLE))
-            + (self.state % (2 * WOBBLE)).saturating_sub(WOBBLE)) as u8;
-        let snake = bits.rotate_right(position as u32);
+        let half = self.period / 2;
+        let phase = self.state % self.period;
+        let stretch = u32::from(self.max_length - self.min_length);
+        let length = u32::from(self.min_length) + stretch * phase.abs_diff(half) / half;
+        let bits = !(0xFFu16 << length) as u8;
+        let advance = u32::from(self.advance);
+        let position =
+            advance * (self.state / self.period % 8) + phase.saturating_sub(half) * advance / half;
+        let snake = bits.rotate_right(position % 8);
         // Reverse most significant nybble
         let value = snake & 0xF
             | ((snake & 0b10000000) >> 3)
@@ -286,42 +1395,1898 @@ impl Display for Snake {
     }
 }
 
-/// Exponential moving average, useful for computing throughput
-#[derive(Debug, Copy, Clone)]
-pub struct MovingAverage {
-    alpha: f32,
-    value: f32,
+/// A spinner that fills and then empties a braille cell one dot at a time in pseudo-random order
+///
+/// Each fill-and-empty cycle takes 16 frames and uses a different order, derived deterministically from the state so
+/// that rendering remains pure. The orders repeat after 256 cycles.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Dissolve {
+    state: u32,
 }
 
-impl MovingAverage {
-    /// `alpha` is in (0, 1] describing how responsive to be to each update
-    pub fn new(alpha: f32, initial: f32) -> Self {
-        Self {
-            alpha,
-            value: initial,
-        }
+impl Dissolve {
+    pub fn new() -> Self {
+        Self { state: 0 }
     }
+}
 
-    /// Update with a new sample
-    pub fn update(&mut self, value: f32) {
-        self.value = self.alpha * value + (1.0 - self.alpha) * self.value;
+impl Spinner for Dissolve {
+    fn set(&mut self, state: u32) {
+        self.state = state % (16 * 256);
+    }
+    fn get(&self) -> u32 {
+        self.state
     }
+    fn step(&mut self, count: u32) {
+        self.state = (self.state + count % (16 * 256)) % (16 * 256);
+    }
+    fn frames(&self) -> u32 {
+        16 * 256
+    }
+}
 
-    /// Get the current average value
-    pub fn get(&self) -> f32 {
-        self.value
+/// Pseudo-random permutation of the bits of a byte, one bit per element
+fn shuffled_bits(seed: u32) -> [u8; 8] {
+    let mut bits = [1, 2, 4, 8, 16, 32, 64, 128];
+    // splitmix32-style hash of the seed followed by xorshift32; neither needs to be of high quality
+    let mut x = seed.wrapping_mul(0x9E37_79B9) ^ 0x85EB_CA6B;
+    x = (x ^ (x >> 16)).wrapping_mul(0x7FEB_352D);
+    x = (x ^ (x >> 15)).wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    for i in (1..bits.len()).rev() {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        bits.swap(i, (x % (i as u32 + 1)) as usize);
     }
+    bits
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Display for Dissolve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cycle = self.state / 16;
+        let phase = self.state % 16;
+        let bits = if phase <= 8 {
+            // Filling
+            shuffled_bits(2 * cycle)[..phase as usize]
+                .iter()
+                .fold(0, |acc, x| acc | x)
+        } else {
+            // Emptying in a different order
+            shuffled_bits(2 * cycle + 1)[phase as usize - 8..]
+                .iter()
+                .fold(0, |acc, x| acc | x)
+        };
+        f.write_char(unsafe { ::std::char::from_u32_unchecked(0x2800 + u32::from(bits)) })
+    }
+}
 
-    #[test]
-    fn bar_sanity() {
-        let mut bar = Bar::new();
-        assert_eq!(format!("{:10}", bar), "          ");
-        bar.set(1.0);
-        assert_eq!(format!("{:10}", bar), "██████████");
+/// A multi-cell spinner in which a snake of braille dots crawls along a track several cells long
+///
+/// The snake winds down and up each column of dots in turn, so it passes through every dot of every cell. Like `Bar`,
+/// the number of cells is taken from the formatter, defaulting to 4. `frames` reports the length of a cycle at the
+/// default width.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut snake = WideSnake::with_length(3);
+/// snake.set(5);
+/// assert_eq!(format!("{:2}", snake), "⣠⠀");
+/// snake.step(4);
+/// assert_eq!(format!("{:2}", snake), "⠈⠃");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+pub struct WideSnake {
+    state: u32,
+    length: u32,
+}
+
+const WIDE_SNAKE_WIDTH: u32 = 4;
+
+impl WideSnake {
+    pub fn new() -> Self {
+        Self::with_length(6)
+    }
+
+    /// Construct a snake `length` dots long
+    pub fn with_length(length: u32) -> Self {
+        Self { state: 0, length }
+    }
+}
+
+impl Default for WideSnake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner for WideSnake {
+    fn set(&mut self, state: u32) {
+        self.state = state;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count);
+    }
+    fn frames(&self) -> u32 {
+        8 * WIDE_SNAKE_WIDTH
+    }
+}
+
+/// Bit of a braille pattern corresponding to the dot at `column` in [0, 2) and `row` in [0, 4)
+fn braille_dot(column: u32, row: u32) -> u8 {
+    match (column, row) {
+        (0, 3) => 0x40,
+        (1, 3) => 0x80,
+        (c, r) => 1 << (3 * c + r),
+    }
+}
+
+impl Display for WideSnake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().map_or(WIDE_SNAKE_WIDTH, |x| x as u32);
+        let path = 8 * width;
+        if path == 0 {
+            return Ok(());
+        }
+        let head = self.state % path;
+        let length = self.length.min(path);
+        for cell in 0..width {
+            let mut bits = 0;
+            for index in 8 * cell..8 * (cell + 1) {
+                // Distance behind the head along the path, wrapping around the end of the track
+                if (head + path - index) % path < length {
+                    let column = index / 4;
+                    let row = if column % 2 == 0 {
+                        index % 4
+                    } else {
+                        3 - index % 4
+                    };
+                    bits |= braille_dot(column % 2, row);
+                }
+            }
+            f.write_char(unsafe { ::std::char::from_u32_unchecked(0x2800 + u32::from(bits)) })?;
+        }
+        Ok(())
+    }
+}
+
+impl WidthHint for WideSnake {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(WIDE_SNAKE_WIDTH as usize)
+    }
+}
+
+/// A multi-cell spinner in which a ball bounces back and forth along a track
+///
+/// Like `Bar`, the width of the track is taken from the formatter, defaulting to 8 cells. `frames` reports the
+/// length of a cycle at the default width.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut bounce = Bounce::new();
+/// bounce.set(2);
+/// assert_eq!(format!("({:6})", bounce), "(  ●   )");
+/// bounce.set(7);
+/// assert_eq!(format!("({:6})", bounce), "(   ●  )");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Bounce {
+    state: u32,
+}
+
+const BOUNCE_WIDTH: u32 = 8;
+
+impl Bounce {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Bounce {
+    fn set(&mut self, state: u32) {
+        self.state = state;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count);
+    }
+    fn frames(&self) -> u32 {
+        2 * (BOUNCE_WIDTH - 1)
+    }
+}
+
+impl Display for Bounce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().map_or(BOUNCE_WIDTH, |x| x as u32);
+        if width == 0 {
+            return Ok(());
+        }
+        let period = (2 * (width - 1)).max(1);
+        let phase = self.state % period;
+        let position = if phase < width { phase } else { period - phase };
+        let fill = f.fill();
+        for i in 0..width {
+            f.write_char(if i == position { '●' } else { fill })?;
+        }
+        Ok(())
+    }
+}
+
+impl WidthHint for Bounce {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(BOUNCE_WIDTH as usize)
+    }
+}
+
+/// A multi-cell spinner in which a bright head sweeps back and forth, leaving a fading trail
+///
+/// Each cell is shaded by how recently the head passed over it, so the trail folds back on itself at the ends of the
+/// track. Like `Bar`, the width of the track is taken from the formatter, defaulting to 8 cells. `frames` reports the
+/// length of a cycle at the default width.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut scanner = Scanner::new();
+/// scanner.set(3);
+/// assert_eq!(format!("[{:6}]", scanner), "[░▒▓█  ]");
+/// scanner.set(6);
+/// assert_eq!(format!("[{:6}]", scanner), "[   ░█▓]");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Scanner {
+    state: u32,
+}
+
+const SCANNER_WIDTH: u32 = 8;
+
+/// Shades for the head and each subsequent step of the trail, brightest first
+const SCANNER_SHADES: [char; 4] = ['█', '▓', '▒', '░'];
+
+impl Scanner {
+    pub fn new() -> Self {
+        Self { state: 0 }
+    }
+}
+
+impl Spinner for Scanner {
+    fn set(&mut self, state: u32) {
+        self.state = state;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        self.state = self.state.wrapping_add(count);
+    }
+    fn frames(&self) -> u32 {
+        2 * (SCANNER_WIDTH - 1)
+    }
+}
+
+impl Display for Scanner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().map_or(SCANNER_WIDTH, |x| x as u32);
+        if width == 0 {
+            return Ok(());
+        }
+        let period = (2 * (width - 1)).max(1);
+        let phase = self.state % period;
+        // Position of the head `age` frames ago
+        let position = |age: u32| {
+            let phase = (phase + period - age % period) % period;
+            if phase < width {
+                phase
+            } else {
+                period - phase
+            }
+        };
+        let fill = f.fill();
+        for i in 0..width {
+            let age = (0..SCANNER_SHADES.len() as u32).find(|&age| position(age) == i);
+            f.write_char(age.map_or(fill, |age| SCANNER_SHADES[age as usize]))?;
+        }
+        Ok(())
+    }
+}
+
+impl WidthHint for Scanner {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(SCANNER_WIDTH as usize)
+    }
+}
+
+/// A multi-cell spinner showing a sine wave travelling to the right
+///
+/// Like `Bar`, the width is taken from the formatter, defaulting to a single 16-cell wavelength. Each frame moves the
+/// wave one cell.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut wave = Wave::with_wavelength(4);
+/// assert_eq!(format!("{:8}", wave), "▅█▄▁▅█▄▁");
+/// wave.step(1);
+/// assert_eq!(format!("{:8}", wave), "▁▅█▄▁▅█▄");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+pub struct Wave {
+    state: u32,
+    wavelength: u32,
+}
+
+const WAVE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+impl Wave {
+    pub fn new() -> Self {
+        Self::with_wavelength(16)
+    }
+
+    /// Construct a wave that repeats every `wavelength` cells
+    ///
+    /// # Panics
+    /// If `wavelength` is zero.
+    pub fn with_wavelength(wavelength: u32) -> Self {
+        assert!(wavelength != 0, "wavelength must be nonzero");
+        Self {
+            state: 0,
+            wavelength,
+        }
+    }
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spinner for Wave {
+    fn set(&mut self, state: u32) {
+        self.state = state % self.wavelength;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        self.state = (self.state + count % self.wavelength) % self.wavelength;
+    }
+    fn frames(&self) -> u32 {
+        self.wavelength
+    }
+}
+
+impl Display for Wave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().map_or(self.wavelength, |x| x as u32);
+        for x in 0..width {
+            let phase = (x + self.wavelength - self.state) % self.wavelength;
+            let angle = 2.0 * ::std::f32::consts::PI * phase as f32 / self.wavelength as f32;
+            let level = ((angle.sin() + 1.0) / 2.0 * (WAVE_LEVELS.len() - 1) as f32).round();
+            f.write_char(WAVE_LEVELS[level as usize])?;
+        }
+        Ok(())
+    }
+}
+
+impl WidthHint for Wave {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(self.wavelength as usize)
+    }
+}
+
+/// A spinner that cycles through caller-supplied frames
+///
+/// Any animation from other spinner collections can be used directly, so long as every frame occupies the same
+/// number of terminal cells.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// const FRAMES: &[&str] = &["◐", "◓", "◑", "◒"];
+/// let mut spinner = CustomSpinner::new(FRAMES).unwrap();
+/// spinner.step(2);
+/// assert_eq!(format!("{}", spinner), "◑");
+/// assert!(CustomSpinner::new(&["a", "bb"]).is_err());
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub struct CustomSpinner<'a> {
+    frames: &'a [&'a str],
+    state: u32,
+}
+
+impl<'a> CustomSpinner<'a> {
+    /// Construct a spinner from `frames`, which must be non-empty and of uniform display width
+    pub fn new(frames: &'a [&'a str]) -> Result<Self, FramesError> {
+        let (first, rest) = frames.split_first().ok_or(FramesError::Empty)?;
+        let expected = width::str_width(first);
+        for (i, frame) in rest.iter().enumerate() {
+            let actual = width::str_width(frame);
+            if actual != expected {
+                return Err(FramesError::Width {
+                    frame: i + 1,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(Self { frames, state: 0 })
+    }
+
+    /// Number of terminal cells occupied by each frame
+    pub fn width(&self) -> usize {
+        width::str_width(self.frames[0])
+    }
+}
+
+impl<'a> Spinner for CustomSpinner<'a> {
+    fn set(&mut self, state: u32) {
+        self.state = state % self.frames.len() as u32;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        let frames = self.frames.len() as u64;
+        self.state = ((u64::from(self.state) + u64::from(count)) % frames) as u32;
+    }
+    fn frames(&self) -> u32 {
+        self.frames.len() as u32
+    }
+}
+
+impl<'a> Display for CustomSpinner<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        width::pad(f, self.frames[self.state as usize])
+    }
+}
+
+impl<'a> WidthHint for CustomSpinner<'a> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        width.unwrap_or(0).max(self.width())
+    }
+}
+
+/// Reasons a set of frames can't be used to construct a `CustomSpinner`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum FramesError {
+    /// No frames were supplied
+    Empty,
+    /// A frame's display width differs from that of the first frame
+    Width {
+        /// Index of the offending frame
+        frame: usize,
+        /// Width of the first frame, in terminal cells
+        expected: usize,
+        /// Width of the offending frame, in terminal cells
+        actual: usize,
+    },
+}
+
+impl Display for FramesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FramesError::Empty => f.write_str("no frames supplied"),
+            FramesError::Width {
+                frame,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "frame {} is {} cells wide, but the first frame is {}",
+                frame, actual, expected
+            ),
+        }
+    }
+}
+
+impl Error for FramesError {}
+
+/// A row of cells evolving under an elementary cellular automaton, one generation per `step`
+///
+/// This is not a `Spinner` because reaching an arbitrary generation requires simulating every generation before it.
+/// The row wraps around at its ends, and live cells are drawn as full blocks and dead cells with the formatter's fill
+/// character.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut automaton = Automaton::new(110, 8);
+/// assert_eq!(format!("{}", automaton), "       █");
+/// automaton.step();
+/// assert_eq!(format!("{}", automaton), "      ██");
+/// automaton.step();
+/// assert_eq!(format!("{}", automaton), "     ███");
+/// ```
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub struct Automaton {
+    cells: u128,
+    width: u32,
+    rule: u8,
+}
+
+impl Automaton {
+    /// Construct a row of `width` cells evolving under the Wolfram code `rule`, seeded with a single live cell at the
+    /// right-hand end
+    ///
+    /// # Panics
+    /// If `width` is not in [1, 128].
+    pub fn new(rule: u8, width: u32) -> Self {
+        Self::with_cells(rule, width, 1)
+    }
+
+    /// Construct a row with an initial state given by the low `width` bits of `cells`, the least significant of which
+    /// is drawn rightmost
+    ///
+    /// # Panics
+    /// If `width` is not in [1, 128].
+    pub fn with_cells(rule: u8, width: u32, cells: u128) -> Self {
+        assert!(
+            width != 0 && width <= 128,
+            "automaton width must be in [1, 128]"
+        );
+        Self {
+            cells: cells & Self::mask(width),
+            width,
+            rule,
+        }
+    }
+
+    fn mask(width: u32) -> u128 {
+        !0 >> (128 - width)
+    }
+
+    /// The current generation's cells, the least significant bit of which is drawn rightmost
+    pub fn cells(&self) -> u128 {
+        self.cells
+    }
+
+    /// Advance by one generation
+    pub fn step(&mut self) {
+        let mut next = 0;
+        for i in 0..self.width {
+            let left = (i + 1) % self.width;
+            let right = (i + self.width - 1) % self.width;
+            let neighborhood = ((self.cells >> left) & 1) << 2
+                | ((self.cells >> i) & 1) << 1
+                | ((self.cells >> right) & 1);
+            next |= u128::from((self.rule >> neighborhood) & 1) << i;
+        }
+        self.cells = next;
+    }
+}
+
+impl Display for Automaton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fill = f.fill();
+        for i in (0..self.width).rev() {
+            f.write_char(if (self.cells >> i) & 1 != 0 {
+                '█'
+            } else {
+                fill
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl WidthHint for Automaton {
+    fn width_hint(&self, _: Option<usize>) -> usize {
+        self.width as usize
+    }
+}
+
+/// Adapter that plays a spinner's animation backwards, e.g. to rotate counter-clockwise
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let spinner = Reversed::new(Spinner4::new());
+/// assert_eq!(spinner.iter_frames().collect::<String>(), "▖▗▝▘");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, Default)]
+pub struct Reversed<S> {
+    inner: S,
+    state: u32,
+}
+
+impl<S: Spinner> Reversed<S> {
+    pub fn new(inner: S) -> Self {
+        let mut result = Self { inner, state: 0 };
+        result.set(0);
+        result
+    }
+
+    /// Access the wrapped spinner
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Recover the wrapped spinner
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Spinner> Spinner for Reversed<S> {
+    fn set(&mut self, state: u32) {
+        self.state = state;
+        // Counting down from a multiple of `frames` rather than reducing by it keeps the animation continuous for
+        // spinners like `Bounce`, whose cycle length depends on the width they're displayed at
+        let frames = self.inner.frames();
+        self.inner
+            .set((u32::MAX / frames * frames).wrapping_sub(state));
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        let state = self.state.wrapping_add(count);
+        self.set(state);
+    }
+    fn frames(&self) -> u32 {
+        self.inner.frames()
+    }
+}
+
+impl<S: Spinner> AddAssign<u32> for Reversed<S> {
+    fn add_assign(&mut self, count: u32) {
+        self.step(count);
+    }
+}
+
+impl<S: Spinner> Add<u32> for Reversed<S> {
+    type Output = Self;
+    fn add(mut self, count: u32) -> Self {
+        self.step(count);
+        self
+    }
+}
+
+impl<S: Display> Display for Reversed<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<S: WidthHint> WidthHint for Reversed<S> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.inner.width_hint(width)
+    }
+}
+
+/// Define `SpinnerStyle` and `AnySpinner` from a list of built-in spinners, each given as the variant name, the
+/// spinner type, and the name accepted by `SpinnerStyle::from_str`
+macro_rules! spinner_styles {
+    ($($(#[$attr:meta])* $variant:ident($ty:ty) = $name:expr),* $(,)*) => {
+        /// Identifies one of the built-in spinners, e.g. for selection at runtime
+        ///
+        /// See the `styles` module for metadata about each style.
+        ///
+        /// # Examples
+        /// ```
+        /// # use yapb::*;
+        /// let style: SpinnerStyle = "snake".parse().unwrap();
+        /// let mut spinner = AnySpinner::new(style);
+        /// spinner.step(1);
+        /// assert_eq!(format!("{}", spinner), format!("{}", Snake::new() + 1));
+        /// ```
+        #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+        pub enum SpinnerStyle {
+            $($(#[$attr])* $variant,)*
+        }
+
+        impl SpinnerStyle {
+            /// Every built-in style, from the least to the most demanding `styles::Charset`, and narrowest first
+            /// within each
+            pub const ALL: &'static [SpinnerStyle] = &[$($(#[$attr])* SpinnerStyle::$variant,)*];
+
+            /// The lowercase name accepted by `from_str`
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($(#[$attr])* SpinnerStyle::$variant => $name,)*
+                }
+            }
+        }
+
+        /// Any of the built-in spinners, with the style chosen at runtime
+        #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+        pub enum AnySpinner {
+            $($(#[$attr])* $variant($ty),)*
+        }
+
+        impl AnySpinner {
+            pub fn new(style: SpinnerStyle) -> Self {
+                match style {
+                    $($(#[$attr])* SpinnerStyle::$variant => AnySpinner::$variant(<$ty>::new()),)*
+                }
+            }
+
+            pub fn style(&self) -> SpinnerStyle {
+                match *self {
+                    $($(#[$attr])* AnySpinner::$variant(_) => SpinnerStyle::$variant,)*
+                }
+            }
+
+            fn as_dyn(&self) -> &dyn Spinner {
+                match *self {
+                    $($(#[$attr])* AnySpinner::$variant(ref x) => x,)*
+                }
+            }
+
+            fn as_width_hint(&self) -> &dyn WidthHint {
+                match *self {
+                    $($(#[$attr])* AnySpinner::$variant(ref x) => x,)*
+                }
+            }
+
+            fn as_dyn_mut(&mut self) -> &mut dyn Spinner {
+                match *self {
+                    $($(#[$attr])* AnySpinner::$variant(ref mut x) => x,)*
+                }
+            }
+        }
+    };
+}
+
+spinner_styles!(
+    Line(Line) = "line",
+    HalfBlock(HalfBlock) = "halfblock",
+    Scanner(Scanner) = "scanner",
+    Counter16(Counter16) = "counter16",
+    Spinner4(Spinner4) = "spinner4",
+    Arrow(Arrow) = "arrow",
+    Trail(Trail) = "trail",
+    Bounce(Bounce) = "bounce",
+    Wave(Wave) = "wave",
+    Counter256(Counter256) = "counter256",
+    Spinner8(Spinner8) = "spinner8",
+    Snake(Snake) = "snake",
+    Dots(Dots) = "dots",
+    Dots2(Dots2) = "dots2",
+    Dots3(Dots3) = "dots3",
+    Dissolve(Dissolve) = "dissolve",
+    WideSnake(WideSnake) = "wide-snake",
+    #[cfg(feature = "emoji")]
+    Moon(emoji::Moon) = "moon",
+    #[cfg(feature = "emoji")]
+    Clock(emoji::Clock) = "clock",
+    #[cfg(feature = "emoji")]
+    Hourglass(emoji::Hourglass) = "hourglass",
+    #[cfg(feature = "emoji")]
+    Globe(emoji::Globe) = "globe",
+    #[cfg(feature = "emoji")]
+    Weather(emoji::Weather) = "weather",
+);
+
+impl Display for SpinnerStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for SpinnerStyle {
+    type Err = ParseStyleError;
+    fn from_str(s: &str) -> Result<Self, ParseStyleError> {
+        SpinnerStyle::ALL
+            .iter()
+            .cloned()
+            .find(|style| style.name().eq_ignore_ascii_case(s))
+            .ok_or(ParseStyleError(()))
+    }
+}
+
+/// Error returned when parsing a `SpinnerStyle` from an unrecognized name
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ParseStyleError(());
+
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown spinner style; expected one of ")?;
+        for (i, style) in SpinnerStyle::ALL.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(style.name())?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseStyleError {}
+
+impl From<SpinnerStyle> for AnySpinner {
+    fn from(style: SpinnerStyle) -> Self {
+        AnySpinner::new(style)
+    }
+}
+
+impl Spinner for AnySpinner {
+    fn set(&mut self, state: u32) {
+        self.as_dyn_mut().set(state);
+    }
+    fn get(&self) -> u32 {
+        self.as_dyn().get()
+    }
+    fn step(&mut self, count: u32) {
+        self.as_dyn_mut().step(count);
+    }
+    fn frames(&self) -> u32 {
+        self.as_dyn().frames()
+    }
+}
+
+impl Display for AnySpinner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_dyn().fmt(f)
+    }
+}
+
+impl WidthHint for AnySpinner {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.as_width_hint().width_hint(width)
+    }
+}
+
+/// Adapter that displays one of two spinners, switching between them periodically or on demand
+///
+/// Both spinners are animated continuously, so e.g. a spinner can periodically flash a warning glyph without losing
+/// its place.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let warning = CustomSpinner::new(&["!"]).unwrap();
+/// let mut spinner = Alternate::new(Spinner4::new(), warning, 2);
+/// assert_eq!(spinner.iter_frames().collect::<String>(), "▖▘!!");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
+pub struct Alternate<A, B> {
+    a: A,
+    b: B,
+    state: u32,
+    period: Option<u32>,
+    showing_b: bool,
+}
+
+impl<A: Spinner, B: Spinner> Alternate<A, B> {
+    /// Show `a` and `b` in turn for `period` frames each
+    ///
+    /// # Panics
+    /// If `period` is zero.
+    pub fn new(a: A, b: B, period: u32) -> Self {
+        assert!(period != 0, "period must be nonzero");
+        Self {
+            a,
+            b,
+            state: 0,
+            period: Some(period),
+            showing_b: false,
+        }
+    }
+
+    /// Show `a` until `show_b` or `toggle` is called
+    pub fn manual(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            state: 0,
+            period: None,
+            showing_b: false,
+        }
+    }
+
+    /// Show the first spinner, switching to manual control
+    pub fn show_a(&mut self) {
+        self.period = None;
+        self.showing_b = false;
+    }
+
+    /// Show the second spinner, switching to manual control
+    pub fn show_b(&mut self) {
+        self.period = None;
+        self.showing_b = true;
+    }
+
+    /// Show whichever spinner is currently hidden, switching to manual control
+    pub fn toggle(&mut self) {
+        let showing_b = self.is_showing_b();
+        self.period = None;
+        self.showing_b = !showing_b;
+    }
+
+    /// Whether the second spinner is currently displayed
+    pub fn is_showing_b(&self) -> bool {
+        match self.period {
+            Some(period) => (self.state / period) % 2 == 1,
+            None => self.showing_b,
+        }
+    }
+
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.a, &self.b)
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: Spinner, B: Spinner> Spinner for Alternate<A, B> {
+    fn set(&mut self, state: u32) {
+        self.a.set(state);
+        self.b.set(state);
+        self.state = state;
+    }
+    fn get(&self) -> u32 {
+        self.state
+    }
+    fn step(&mut self, count: u32) {
+        self.a.step(count);
+        self.b.step(count);
+        self.state = self.state.wrapping_add(count);
+    }
+    fn frames(&self) -> u32 {
+        let mut frames = lcm(u64::from(self.a.frames()), u64::from(self.b.frames()));
+        if let Some(period) = self.period {
+            frames = lcm(frames, 2 * u64::from(period));
+        }
+        frames.min(u64::from(u32::MAX)) as u32
+    }
+}
+
+impl<A: Spinner, B: Spinner> AddAssign<u32> for Alternate<A, B> {
+    fn add_assign(&mut self, count: u32) {
+        self.step(count);
+    }
+}
+
+impl<A: Spinner, B: Spinner> Add<u32> for Alternate<A, B> {
+    type Output = Self;
+    fn add(mut self, count: u32) -> Self {
+        self.step(count);
+        self
+    }
+}
+
+impl<A: Spinner, B: Spinner> Display for Alternate<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_showing_b() {
+            self.b.fmt(f)
+        } else {
+            self.a.fmt(f)
+        }
+    }
+}
+
+impl<A: Spinner + WidthHint, B: Spinner + WidthHint> WidthHint for Alternate<A, B> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        if self.is_showing_b() {
+            self.b.width_hint(width)
+        } else {
+            self.a.width_hint(width)
+        }
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while a != 0 {
+        let t = b % a;
+        b = a;
+        a = t;
+    }
+    b
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+/// Exponential moving average, useful for computing throughput
+#[derive(Debug, Copy, Clone)]
+pub struct MovingAverage {
+    alpha: f32,
+    value: f32,
+}
+
+impl MovingAverage {
+    /// `alpha` is in (0, 1] describing how responsive to be to each update
+    pub fn new(alpha: f32, initial: f32) -> Self {
+        Self {
+            alpha,
+            value: initial,
+        }
+    }
+
+    /// Construct an average in which a sample's influence halves after each `samples` further updates
+    pub fn with_half_life(samples: f32, initial: f32) -> Self {
+        Self::new(1.0 - 0.5f32.powf(1.0 / samples), initial)
+    }
+
+    /// Update with a new sample
+    pub fn update(&mut self, value: f32) {
+        self.value = self.alpha * value + (1.0 - self.alpha) * self.value;
+    }
+
+    /// Get the current average value
+    pub fn get(&self) -> f32 {
+        self.value
+    }
+
+    /// Discard the history of samples, e.g. to reuse the average for a new task
+    pub fn reset(&mut self, initial: f32) {
+        self.value = initial;
+    }
+}
+
+/// Double-precision variant of `MovingAverage`, for quantities such as byte counts that exceed the 24 bits of precision
+/// offered by `f32`
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut average = MovingAverage64::new(0.5, 0.0);
+/// average.update(1e9 + 1.0);
+/// average.update(1e9 + 1.0);
+/// assert_eq!(average.get(), 7.5e8 + 0.75);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct MovingAverage64 {
+    alpha: f64,
+    value: f64,
+}
+
+impl MovingAverage64 {
+    /// `alpha` is in (0, 1] describing how responsive to be to each update
+    pub fn new(alpha: f64, initial: f64) -> Self {
+        Self {
+            alpha,
+            value: initial,
+        }
+    }
+
+    /// Construct an average in which a sample's influence halves after each `samples` further updates
+    pub fn with_half_life(samples: f64, initial: f64) -> Self {
+        Self::new(1.0 - 0.5f64.powf(1.0 / samples), initial)
+    }
+
+    /// Update with a new sample
+    pub fn update(&mut self, value: f64) {
+        self.value = self.alpha * value + (1.0 - self.alpha) * self.value;
+    }
+
+    /// Get the current average value
+    pub fn get(&self) -> f64 {
+        self.value
+    }
+
+    /// Discard the history of samples, e.g. to reuse the average for a new task
+    pub fn reset(&mut self, initial: f64) {
+        self.value = initial;
+    }
+}
+
+/// Exponential moving average of samples taken at irregular intervals
+///
+/// Each sample's weight grows with the time elapsed since the previous one, so the result doesn't depend on how often
+/// updates arrive. This is equivalent to a `MovingAverage` whose `alpha` is `1 - exp(-elapsed / time_constant)`.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use std::time::Duration;
+/// let mut average = TimeWeightedAverage::new(Duration::from_secs(1), 0.0);
+/// // Many frequent samples...
+/// for _ in 0..10 {
+///     average.update(1.0, Duration::from_millis(100));
+/// }
+/// let mut other = TimeWeightedAverage::new(Duration::from_secs(1), 0.0);
+/// // ...have the same effect as one sample covering the same period
+/// other.update(1.0, Duration::from_secs(1));
+/// assert!((average.get() - other.get()).abs() < 1e-6);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct TimeWeightedAverage {
+    time_constant: f32,
+    value: f32,
+}
+
+impl TimeWeightedAverage {
+    /// `time_constant` is the time over which the influence of a sample decays by a factor of e
+    pub fn new(time_constant: Duration, initial: f32) -> Self {
+        Self {
+            time_constant: time_constant.as_secs_f32(),
+            value: initial,
+        }
+    }
+
+    /// Construct an average in which a sample's influence halves over each `half_life`
+    pub fn with_half_life(half_life: Duration, initial: f32) -> Self {
+        Self {
+            time_constant: half_life.as_secs_f32() / ::std::f32::consts::LN_2,
+            value: initial,
+        }
+    }
+
+    /// Update with a new sample, taken `elapsed` after the previous one
+    pub fn update(&mut self, value: f32, elapsed: Duration) {
+        let alpha = 1.0 - (-elapsed.as_secs_f32() / self.time_constant).exp();
+        self.value = alpha * value + (1.0 - alpha) * self.value;
+    }
+
+    /// Get the current average value
+    pub fn get(&self) -> f32 {
+        self.value
+    }
+
+    /// Discard the history of samples, e.g. to reuse the average for a new task
+    pub fn reset(&mut self, initial: f32) {
+        self.value = initial;
+    }
+}
+
+/// Average of the most recent samples within a fixed-size window
+///
+/// Unlike `MovingAverage`, every sample in the window has equal weight and older samples have none, which makes the
+/// result easier to reason about and allows the extremes of the window to be reported.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// let mut average = WindowedAverage::new(3);
+/// for &x in &[4.0, 1.0, 2.0, 3.0] {
+///     average.update(x);
+/// }
+/// assert_eq!(average.get(), 2.0);
+/// assert_eq!(average.min(), Some(1.0));
+/// assert_eq!(average.max(), Some(3.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowedAverage {
+    samples: Vec<f32>,
+    capacity: usize,
+    /// Index of the oldest sample once the window is full
+    next: usize,
+}
+
+impl WindowedAverage {
+    /// Construct an average over the `capacity` most recent samples
+    ///
+    /// # Panics
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity != 0, "capacity must be nonzero");
+        Self {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            next: 0,
+        }
+    }
+
+    /// Update with a new sample, displacing the oldest if the window is full
+    pub fn update(&mut self, value: f32) {
+        if self.samples.len() < self.capacity {
+            self.samples.push(value);
+        } else {
+            self.samples[self.next] = value;
+            self.next = (self.next + 1) % self.capacity;
+        }
+    }
+
+    /// Get the mean of the samples in the window, or 0 if there are none
+    pub fn get(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<f32>() / self.samples.len() as f32
+    }
+
+    /// Smallest sample in the window
+    pub fn min(&self) -> Option<f32> {
+        self.samples
+            .iter()
+            .cloned()
+            .fold(None, |acc, x| Some(acc.map_or(x, |y: f32| y.min(x))))
+    }
+
+    /// Largest sample in the window
+    pub fn max(&self) -> Option<f32> {
+        self.samples
+            .iter()
+            .cloned()
+            .fold(None, |acc, x| Some(acc.map_or(x, |y: f32| y.max(x))))
+    }
+
+    /// Number of samples currently in the window
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Maximum number of samples in the window
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Discard all samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.next = 0;
+    }
+
+    /// Iterate over the samples in the window from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        let (newer, older) = self.samples.split_at(self.next);
+        older.iter().chain(newer).cloned()
+    }
+}
+
+/// Smoothed rate at which some quantity, e.g. bytes transferred, is accumulating
+///
+/// Increments are accumulated for at least `interval` before being folded into the average, so bursts of rapid
+/// updates don't produce spurious spikes. Time that passes without progress is accounted for by calling `tick`, which
+/// allows the rate to decay smoothly during idle periods.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut throughput = Throughput::new(0.5, Duration::from_millis(100));
+/// throughput.record(start, 0);
+/// throughput.record(start + Duration::from_secs(1), 2048);
+/// assert_eq!(throughput.get(), 2048.0);
+/// assert_eq!(format!("{}", throughput.binary("B")), "2.00 KiB/s");
+/// assert_eq!(format!("{}", throughput.scientific("req")), "2.05 kreq/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Throughput {
+    alpha: f32,
+    interval: Duration,
+    /// Smoothed rate, in double precision to accommodate large counts such as byte totals
+    rate: Option<f64>,
+    last: Option<Instant>,
+    pending: u64,
+}
+
+impl Throughput {
+    /// `alpha` is in (0, 1] describing how responsive to be to each `interval` worth of updates
+    pub fn new(alpha: f32, interval: Duration) -> Self {
+        Self {
+            alpha,
+            interval,
+            rate: None,
+            last: None,
+            pending: 0,
+        }
+    }
+
+    /// Record that `count` additional units have accumulated as of `now`
+    ///
+    /// The first call establishes the start time.
+    pub fn record(&mut self, now: Instant, count: u64) {
+        self.pending += count;
+        let last = match self.last {
+            None => {
+                self.last = Some(now);
+                return;
+            }
+            Some(x) => x,
+        };
+        let elapsed = now.saturating_duration_since(last);
+        if elapsed < self.interval || elapsed == Duration::from_secs(0) {
+            return;
+        }
+        let rate = self.pending as f64 / elapsed.as_secs_f64();
+        // Weight the sample by the number of intervals it spans, so that a long gap has as much influence as the same
+        // period of regular updates
+        let intervals = if self.interval == Duration::from_secs(0) {
+            1
+        } else {
+            (elapsed.as_nanos() / self.interval.as_nanos()).min(i32::MAX as u128) as i32
+        };
+        self.rate = Some(match self.rate {
+            None => rate,
+            Some(average) => {
+                let retained = (1.0 - f64::from(self.alpha)).powi(intervals);
+                rate + (average - rate) * retained
+            }
+        });
+        self.last = Some(now);
+        self.pending = 0;
+    }
+
+    /// Record that no additional units have accumulated as of `now`
+    pub fn tick(&mut self, now: Instant) {
+        self.record(now, 0);
+    }
+
+    /// Smoothed rate in units per second, or 0 if less than one interval has elapsed
+    pub fn get(&self) -> f64 {
+        self.rate.unwrap_or(0.0)
+    }
+
+    /// Display the rate in `unit`s per second using binary prefixes, e.g. for bytes
+    pub fn binary<'a>(&self, unit: &'a str) -> impl Display + 'a {
+        PerSecond {
+            rate: self.get(),
+            unit,
+            binary: true,
+        }
+    }
+
+    /// Display the rate in `unit`s per second using SI prefixes
+    pub fn scientific<'a>(&self, unit: &'a str) -> impl Display + 'a {
+        PerSecond {
+            rate: self.get(),
+            unit,
+            binary: false,
+        }
+    }
+}
+
+/// Displays the rate using SI prefixes, without a unit
+impl Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.scientific("").fmt(f)
+    }
+}
+
+struct PerSecond<'a> {
+    rate: f64,
+    unit: &'a str,
+    binary: bool,
+}
+
+impl<'a> Display for PerSecond<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.binary {
+            write!(f, "{}{}/s", prefix::Binary(self.rate), self.unit)
+        } else {
+            write!(f, "{}{}/s", prefix::Scientific(self.rate), self.unit)
+        }
+    }
+}
+
+/// Running count of processed items, displayed with its smoothed rate, e.g. `1 204 items, 356 items/s`
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut items = ItemRate::new("items", 0.5, Duration::from_millis(100));
+/// items.set(start, 0);
+/// items.set(start + Duration::from_secs(1), 1204);
+/// assert_eq!(format!("{}", items), "1 204 items, 1.20 kitems/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ItemRate<'a> {
+    unit: &'a str,
+    total: u64,
+    throughput: Throughput,
+}
+
+impl<'a> ItemRate<'a> {
+    /// Count `unit`s, smoothing the rate as described by `Throughput::new`
+    pub fn new(unit: &'a str, alpha: f32, interval: Duration) -> Self {
+        Self {
+            unit,
+            total: 0,
+            throughput: Throughput::new(alpha, interval),
+        }
+    }
+
+    /// Record that `count` more items have been processed as of `now`
+    pub fn add(&mut self, now: Instant, count: u64) {
+        self.total += count;
+        self.throughput.record(now, count);
+    }
+
+    /// Record that `total` items have been processed as of `now`
+    ///
+    /// Totals less than the current total are ignored.
+    pub fn set(&mut self, now: Instant, total: u64) {
+        let count = total.saturating_sub(self.total);
+        self.add(now, count);
+    }
+
+    /// Record that no more items have been processed as of `now`
+    pub fn tick(&mut self, now: Instant) {
+        self.throughput.tick(now);
+    }
+
+    /// Number of items processed
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Smoothed rate in items per second
+    pub fn rate(&self) -> f64 {
+        self.throughput.get()
+    }
+}
+
+impl<'a> Display for ItemRate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}, ", prefix::Grouped(self.total, ' '), self.unit)?;
+        let rate = self.rate();
+        if rate < 1.0 {
+            // Sub-unit prefixes would read oddly for counts of items
+            write!(f, "{:.2} {}/s", rate, self.unit)
+        } else {
+            write!(f, "{}", prefix::Rate(rate, self.unit))
+        }
+    }
+}
+
+/// Tracks how long it has been since progress last changed, so quiet transfers can be flagged
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut stall = StallDetector::new();
+/// stall.update(start, 0.5);
+/// let later = start + Duration::from_secs(12);
+/// stall.update(later, 0.5);
+/// assert!(stall.is_stalled(later, Duration::from_secs(10)));
+/// assert_eq!(format!("{}", stall.status(later, Duration::from_secs(10))), "(stalled 12s)");
+/// stall.update(later, 0.6);
+/// assert_eq!(format!("{}", stall.status(later, Duration::from_secs(10))), "");
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct StallDetector {
+    last: Option<(Instant, f32)>,
+}
+
+impl StallDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that progress is `value` as of `now`
+    ///
+    /// Only changes in `value` reset the stall timer; the first call establishes the start time.
+    pub fn update(&mut self, now: Instant, value: f32) {
+        match self.last {
+            Some((_, last)) if last == value => {}
+            _ => self.last = Some((now, value)),
+        }
+    }
+
+    /// Time elapsed between the most recent change in progress and `now`
+    pub fn since_change(&self, now: Instant) -> Duration {
+        self.last.map_or(Duration::from_secs(0), |(changed, _)| {
+            now.saturating_duration_since(changed)
+        })
+    }
+
+    /// Whether progress has not changed for at least `threshold`
+    pub fn is_stalled(&self, now: Instant, threshold: Duration) -> bool {
+        self.last.is_some() && self.since_change(now) >= threshold
+    }
+
+    /// Display e.g. `(stalled 12s)` if stalled as of `now`, or nothing otherwise
+    pub fn status(&self, now: Instant, threshold: Duration) -> impl Display {
+        Stalled(if self.is_stalled(now, threshold) {
+            Some(self.since_change(now))
+        } else {
+            None
+        })
+    }
+}
+
+struct Stalled(Option<Duration>);
+
+impl Display for Stalled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(x) => write!(f, "(stalled {}s)", x.as_secs()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Decides when an indicator is worth redrawing, so that rapid updates don't flood the terminal
+///
+/// A redraw is permitted once at least the minimum interval has passed since the previous one and progress has moved
+/// by at least the minimum delta. Reaching completion is always drawn, so the final state is never lost.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut gate = RedrawGate::new(Duration::from_millis(50)).with_min_delta(0.01);
+/// assert!(gate.ready(start, 0.0));
+/// assert!(!gate.ready(start + Duration::from_millis(10), 0.5));
+/// assert!(!gate.ready(start + Duration::from_millis(60), 0.001));
+/// assert!(gate.ready(start + Duration::from_millis(60), 0.5));
+/// assert!(gate.ready(start + Duration::from_millis(61), 1.0));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RedrawGate {
+    min_interval: Duration,
+    min_delta: f32,
+    last: Option<(Instant, f32)>,
+}
+
+impl RedrawGate {
+    /// Permit at most one redraw per `min_interval`
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            min_delta: 0.0,
+            last: None,
+        }
+    }
+
+    /// Additionally require progress to move by at least `delta` between redraws
+    pub fn with_min_delta(self, delta: f32) -> Self {
+        Self {
+            min_delta: delta,
+            ..self
+        }
+    }
+
+    /// Whether to redraw an indicator showing `value` as of `now`
+    ///
+    /// A `true` result is assumed to be acted upon, and becomes the reference point for subsequent calls.
+    pub fn ready(&mut self, now: Instant, value: f32) -> bool {
+        let ready = match self.last {
+            None => true,
+            Some((_, last)) if value >= 1.0 && last < 1.0 => true,
+            Some((time, last)) => {
+                now.saturating_duration_since(time) >= self.min_interval
+                    && (value - last).abs() >= self.min_delta
+            }
+        };
+        if ready {
+            self.last = Some((now, value));
+        }
+        ready
+    }
+
+    /// Permit the next redraw unconditionally, e.g. after the terminal was resized
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_sanity() {
+        let mut bar = Bar::new();
+        assert_eq!(format!("{:10}", bar), "          ");
+        bar.set(1.0);
+        assert_eq!(format!("{:10}", bar), "██████████");
+    }
+
+    #[test]
+    fn bar_fixed_point() {
+        let mut bar = Bar::new();
+        bar.set(0.5);
+        assert_eq!(bar.get(), 0.5);
+        assert_eq!(format!("{:3}", bar), "█▌ ");
+        bar.set(2.0);
+        assert_eq!(bar.get(), 1.0);
+        bar.set(f32::NAN);
+        assert_eq!(bar, Bar::new());
+    }
+
+    #[test]
+    fn bar_styles() {
+        let mut bar = Bar::with_style(BarStyle::ASCII);
+        bar.set(0.3);
+        assert_eq!(format!("{:5}", bar), "#-   ");
+        bar.set(1.0);
+        assert_eq!(format!("{:5}", bar), "#####");
+        let mut bar = Bar::with_style(BarStyle {
+            full: "=",
+            partial: &[],
+            empty: Some("."),
+        });
+        bar.set(0.5);
+        assert_eq!(format!("{:5}", bar), "==...");
+        // Clusters of several code points are drawn whole, and measured by their width
+        let mut bar = Bar::with_style(BarStyle {
+            full: "❤\u{FE0F}",
+            partial: &["♡\u{FE0F}"],
+            empty: None,
+        });
+        bar.set(0.85);
+        assert_eq!(format!("{:-<7}", bar), "❤\u{FE0F}❤\u{FE0F}♡\u{FE0F}-");
+        assert_eq!(width::str_width(&format!("{:-<7}", bar)), 7);
+        let mut bar = Bar::with_style(BarStyle::FULLWIDTH);
+        for &fraction in &[0.0, 0.33, 0.5, 1.0] {
+            bar.set(fraction);
+            for width in 0..8 {
+                assert_eq!(width::str_width(&format!("{:1$}", bar, width)), width);
+            }
+        }
+    }
+
+    #[test]
+    fn bordered() {
+        let mut bar = Bordered::new(Bar::with_style(BarStyle::ASCII), Border::BRACKETS);
+        bar.set(0.3);
+        assert_eq!(format!("{:7}", bar), "[#-   ]");
+        assert_eq!(format!("{:.>7}", bar), "[#-...]");
+        assert_eq!(format!("{:1}", bar), "[]");
+        assert_eq!(bar.to_string().len(), 80);
+        let bar = Bordered::new(Bar::with_style(BarStyle::FULLWIDTH), Border::LIGHT);
+        assert_eq!(format!("{:7}", bar), "│     │");
+        let mut bar = Bar::with_style(BarStyle::ASCII).framed("<<", "〉");
+        bar.set(1.0);
+        assert_eq!(format!("{:7}", bar), "<<###〉");
+    }
+
+    #[test]
+    fn animator_exponential() {
+        let mut bar = Animator::new(Bar::new(), Easing::Exponential(0.5));
+        bar.set(1.0);
+        assert!(bar.tick());
+        assert_eq!(bar.displayed(), 0.5);
+        assert!(bar.tick());
+        assert_eq!(bar.displayed(), 0.75);
+        while bar.tick() {}
+        assert_eq!(bar.displayed(), 1.0);
+        bar.set(0.0);
+        bar.finish();
+        assert_eq!(bar.get_ref().get(), 0.0);
+    }
+
+    #[test]
+    fn aggregate() {
+        let mut total = Aggregate::new(Bar::new());
+        assert_eq!(total.get(), 0.0);
+        total.add(1, 1.0);
+        total.add(2, 0.0);
+        assert!(total.update(&1, 0.5));
+        assert!(!total.update(&3, 0.5));
+        assert_eq!(total.get(), 0.5);
+        total.add(2, 1.0);
+        assert_eq!(total.get(), 0.25);
+        assert_eq!(total.get_ref().get(), 0.25);
+        total.set(1.0);
+        assert_eq!(total.child(&2), Some(1.0));
+        assert_eq!(total.remove(&1), Some(1.0));
+        assert_eq!(total.get(), 1.0);
+    }
+
+    #[test]
+    fn half_life() {
+        let mut average = MovingAverage::with_half_life(2.0, 1.0);
+        average.update(0.0);
+        average.update(0.0);
+        assert!((average.get() - 0.5).abs() < 1e-6);
+        average.reset(3.0);
+        assert_eq!(average.get(), 3.0);
+
+        let mut average = TimeWeightedAverage::with_half_life(Duration::from_secs(10), 1.0);
+        average.update(0.0, Duration::from_secs(10));
+        assert!((average.get() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn item_rate() {
+        let start = Instant::now();
+        let mut items = ItemRate::new("files", 1.0, Duration::from_secs(1));
+        items.set(start, 0);
+        assert_eq!(format!("{}", items), "0 files, 0.00 files/s");
+        items.add(start + Duration::from_secs(2), 1);
+        items.set(start + Duration::from_secs(2), 0);
+        assert_eq!(format!("{}", items), "1 files, 0.50 files/s");
+        items.set(start + Duration::from_secs(3), 123_456);
+        assert_eq!(items.total(), 123_456);
+        assert!(format!("{}", items).starts_with("123 456 files, "));
+    }
+
+    #[test]
+    fn spinner_ops() {
+        let mut a = Spinner8::new();
+        a += 3;
+        let mut b = Spinner8::new();
+        b.step(3);
+        assert_eq!(a, b);
+        assert_eq!(a + 5, Spinner8::new());
+    }
+
+    fn assert_cycles<S: Spinner + Clone>(spinner: S) {
+        let frames = spinner.iter_frames().collect::<Vec<_>>();
+        assert_eq!(frames.len(), spinner.frames() as usize);
+        let mut spinner = spinner;
+        for (i, frame) in frames.iter().enumerate() {
+            spinner.set(i as u32 + spinner.frames());
+            assert_eq!(&spinner.to_string(), frame);
+            assert_eq!(spinner.get() % spinner.frames(), i as u32);
+        }
+    }
+
+    #[test]
+    fn spinner_frames() {
+        assert_cycles(Counter256::new());
+        assert_cycles(Spinner8::new());
+        assert_cycles(Counter16::new());
+        assert_cycles(Spinner4::new());
+        assert_cycles(Snake::new());
+        assert_cycles(Reversed::new(Snake::new()));
+        assert_cycles(Snake::with_params(2, 8, 4, 3));
+        assert_cycles(Snake::with_params(3, 3, 2, 0));
+        assert_cycles(CustomSpinner::new(&["-", "=", "≡"]).unwrap());
+        for &style in SpinnerStyle::ALL {
+            assert_cycles(AnySpinner::new(style));
+        }
+    }
+
+    #[test]
+    fn style_names() {
+        for &style in SpinnerStyle::ALL {
+            assert_eq!(style.name().parse(), Ok(style));
+            assert_eq!(AnySpinner::new(style).style(), style);
+        }
+        assert_eq!("Snake".parse(), Ok(SpinnerStyle::Snake));
+        assert!("bogus".parse::<SpinnerStyle>().is_err());
+    }
+
+    /// Check a widget's `WidthHint` against the width of its rendering
+    fn assert_width_hint<T: Display + WidthHint>(x: &T) {
+        assert_eq!(width::str_width(&x.to_string()), x.width_hint(None));
+        for n in [0, 1, 5] {
+            assert_eq!(
+                width::str_width(&format!("{:1$}", x, n)),
+                x.width_hint(Some(n))
+            );
+        }
+    }
+
+    #[test]
+    fn width_hints() {
+        assert_width_hint(&Bar::new());
+        assert_width_hint(&Bordered::default());
+        assert_width_hint(&Automaton::new(30, 12));
+        assert_width_hint(&Reversed::new(Wave::new()));
+        assert_width_hint(&Alternate::new(Spinner4::new(), Bounce::new(), 10));
+        assert_width_hint(&CustomSpinner::new(&["<->", "<=>"]).unwrap());
+        for &style in SpinnerStyle::ALL {
+            assert_width_hint(&AnySpinner::new(style));
+        }
+    }
+
+    #[test]
+    fn step_large() {
+        let mut a = Dots::new();
+        a.step(1000);
+        let mut b = Dots::new();
+        b.set(1000);
+        assert_eq!(a, b);
+        assert_eq!(a, Dots::new());
+        b.set(256);
+        assert_eq!(b, Dots::new() + 6);
+    }
+
+    #[test]
+    fn gray_code() {
+        let frames = Counter256::gray().iter_frames().collect::<Vec<_>>();
+        for (a, b) in frames.iter().zip(frames.iter().cycle().skip(1)) {
+            let a = a.chars().next().unwrap() as u32;
+            let b = b.chars().next().unwrap() as u32;
+            assert_eq!((a ^ b).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn windowed_order() {
+        let mut average = WindowedAverage::new(3);
+        assert_eq!(average.get(), 0.0);
+        assert_eq!(average.min(), None);
+        for x in 0..5 {
+            average.update(x as f32);
+        }
+        assert_eq!(average.iter().collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+        assert_eq!(average.get(), 3.0);
+    }
+
+    #[test]
+    fn throughput_idle() {
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+        let mut throughput = Throughput::new(0.5, second);
+        throughput.record(start, 0);
+        throughput.record(start + second / 2, 50);
+        assert_eq!(throughput.get(), 0.0);
+        throughput.record(start + second, 50);
+        assert_eq!(throughput.get(), 100.0);
+        // Three idle seconds decay as much as three separate samples of 0 would
+        throughput.tick(start + 4 * second);
+        assert_eq!(throughput.get(), 12.5);
+    }
+
+    #[test]
+    fn dissolve() {
+        let mut spinner = Dissolve::new();
+        let bits = |x: &Dissolve| x.to_string().chars().next().unwrap() as u32 - 0x2800;
+        let mut previous = bits(&spinner);
+        let mut counts = Vec::new();
+        for _ in 0..spinner.frames() {
+            spinner.step(1);
+            let current = bits(&spinner);
+            // Exactly one dot appears or disappears per frame, including across cycles
+            assert_eq!((current ^ previous).count_ones(), 1);
+            counts.push(current.count_ones());
+            previous = current;
+            if counts.len() == 16 {
+                counts.sort();
+                counts.dedup();
+                assert_eq!(counts, (0..=8).collect::<Vec<_>>());
+                counts.clear();
+            }
+        }
+        assert_eq!(spinner.get(), 0);
+    }
+
+    #[test]
+    fn alternate() {
+        let mut spinner = Alternate::manual(Line::new(), Spinner4::new());
+        assert_cycles(spinner);
+        spinner.step(1);
+        assert_eq!(spinner.to_string(), "/");
+        spinner.toggle();
+        assert_eq!(spinner.to_string(), "▘");
+        assert_cycles(Alternate::new(Dots::new(), Spinner4::new(), 3));
+    }
+
+    #[test]
+    fn reversed() {
+        let forward = Spinner8::new().iter_frames().collect::<Vec<_>>();
+        let mut spinner = Reversed::new(Spinner8::new());
+        for i in 0..16 {
+            assert_eq!(spinner.to_string(), forward[(8 - i % 8) % 8]);
+            spinner.step(1);
+        }
+    }
+
+    #[test]
+    fn set_time_wide() {
+        // At 20 cells a cycle is longer than the 14 frames reported for the default width, so the head must still
+        // reach the far end and move one cell per frame throughout
+        fn head(frame: String, c: char) -> usize {
+            frame.chars().position(|x| x == c).unwrap()
+        }
+        let (mut bounce, mut scanner) = (Bounce::new(), Scanner::new());
+        let mut reversed = Reversed::new(Bounce::new());
+        let mut previous = None;
+        let mut furthest = 0;
+        for i in 0..40 {
+            let elapsed = Duration::from_millis(100 * i);
+            bounce.set_time(elapsed, 10.0);
+            scanner.set_time(elapsed, 10.0);
+            reversed.set_time(elapsed, 10.0);
+            let position = head(format!("{:20}", bounce), '●');
+            assert_eq!(head(format!("{:20}", scanner), '█'), position);
+            let backwards = head(format!("{:20}", reversed), '●');
+            if let Some((position_before, backwards_before)) = previous {
+                assert_eq!(position.abs_diff(position_before), 1);
+                assert_eq!(backwards.abs_diff(backwards_before), 1);
+            }
+            previous = Some((position, backwards));
+            furthest = furthest.max(position);
+        }
+        assert_eq!(furthest, 19);
     }
 }
@@ -0,0 +1,168 @@
+//! Progress reported as `log` records
+//!
+//! Applications that already send their output through a logger can report progress the same way, rather than
+//! managing a second output path for an indicator.
+
+extern crate log;
+
+use std::time::{Duration, Instant};
+
+use self::log::Level;
+
+use duration::Human;
+use plain::PlainReporter;
+use StallDetector;
+
+/// Logs progress milestones, stalls, and a summary on completion
+///
+/// Milestones are chosen by a `PlainReporter`, every 10% or 30 seconds by default. A stall is logged once each time
+/// progress stops changing for the stall threshold.
+///
+/// # Examples
+/// ```
+/// # use yapb::log::Logger;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut logger = Logger::new("myapp::download");
+/// // Logs `0% fetching (0ms)` at level `Info`
+/// logger.update(start, 0.0, "fetching");
+/// // Logs `100% fetched (2s)`
+/// logger.finish(start + Duration::from_secs(2), "fetched");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Logger<'a> {
+    target: &'a str,
+    milestones: PlainReporter,
+    stall: StallDetector,
+    stall_threshold: Duration,
+    stall_reported: bool,
+    progress_level: Level,
+    stall_level: Level,
+    finish_level: Level,
+}
+
+impl<'a> Logger<'a> {
+    /// Log to `target`, with milestones and completion at `Info` and stalls of 30 seconds at `Warn`
+    pub fn new(target: &'a str) -> Self {
+        Self {
+            target,
+            milestones: PlainReporter::new(),
+            stall: StallDetector::new(),
+            stall_threshold: Duration::from_secs(30),
+            stall_reported: false,
+            progress_level: Level::Info,
+            stall_level: Level::Warn,
+            finish_level: Level::Info,
+        }
+    }
+
+    /// Use `milestones` to decide when progress is logged
+    pub fn with_milestones(mut self, milestones: PlainReporter) -> Self {
+        self.milestones = milestones;
+        self
+    }
+
+    /// Log a stall once progress hasn't changed for `threshold`
+    pub fn with_stall_threshold(mut self, threshold: Duration) -> Self {
+        self.stall_threshold = threshold;
+        self
+    }
+
+    /// Log milestones at `level`
+    pub fn with_progress_level(mut self, level: Level) -> Self {
+        self.progress_level = level;
+        self
+    }
+
+    /// Log stalls at `level`
+    pub fn with_stall_level(mut self, level: Level) -> Self {
+        self.stall_level = level;
+        self
+    }
+
+    /// Log the completion summary at `level`
+    pub fn with_finish_level(mut self, level: Level) -> Self {
+        self.finish_level = level;
+        self
+    }
+
+    /// Record that progress has reached `fraction` at `now`, logging any milestone or stall
+    pub fn update(&mut self, now: Instant, fraction: f32, message: &str) {
+        self.stall.update(now, fraction);
+        if !self.stall.is_stalled(now, self.stall_threshold) {
+            self.stall_reported = false;
+        } else if !self.stall_reported {
+            self.stall_reported = true;
+            let percent = (fraction.clamp(0.0, 1.0) * 100.0).floor();
+            log::log!(
+                target: self.target,
+                self.stall_level,
+                "{}% {} (stalled {})",
+                percent,
+                message,
+                Human(self.stall.since_change(now))
+            );
+        }
+        if let Some(line) = self.milestones.update(now, fraction, message) {
+            log::log!(target: self.target, self.progress_level, "{}", line);
+        }
+    }
+
+    /// Log a summary of the work completing at `now`
+    pub fn finish(&mut self, now: Instant, message: &str) {
+        let line = self.milestones.finish(now, message);
+        log::log!(target: self.target, self.finish_level, "{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::log::{Log, Metadata, Record};
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Capture(Mutex<Vec<String>>);
+
+    impl Log for Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            if record.target() == "yapb::test" {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", record.level(), record.args()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+    #[test]
+    fn records() {
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        let start = Instant::now();
+        let mut logger = Logger::new("yapb::test")
+            .with_milestones(PlainReporter::new().with_step(0.5))
+            .with_stall_threshold(Duration::from_secs(5))
+            .with_progress_level(Level::Debug);
+        for i in 0..10 {
+            logger.update(start + Duration::from_secs(i), 0.5, "copying");
+        }
+        logger.update(start + Duration::from_secs(11), 0.75, "copying");
+        logger.finish(start + Duration::from_secs(12), "copied");
+        assert_eq!(
+            *CAPTURE.0.lock().unwrap(),
+            [
+                "DEBUG 50% copying (0ms)",
+                "WARN 50% copying (stalled 5s)",
+                "INFO 100% copied (12s)"
+            ]
+        );
+    }
+}
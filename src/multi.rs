@@ -0,0 +1,487 @@
+//! Several concurrent tasks displayed as a block of lines
+
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use duration::Human;
+use layout::{Columns, Item, Row};
+use prefix::Rate;
+use width;
+use {AnySpinner, Bar, Progress, Spinner, SpinnerStyle, Throughput};
+
+/// A block of task lines, each with a label, a bar or spinner, and free-form statistics
+///
+/// Rendering produces one line per task, with labels aligned in a column. Moving the cursor back over the previous
+/// block before writing the next one is left to the caller.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::multi::MultiBar;
+/// let mut tasks = MultiBar::new();
+/// let download = tasks.add_bar("download");
+/// tasks.add_spinner("unpack", SpinnerStyle::Line);
+/// tasks.get_mut(download).unwrap().set(0.5);
+/// tasks.get_mut(download).unwrap().set_stats("5/10 MiB");
+/// assert_eq!(
+///     tasks.render(30),
+///     "download [█████     ] 5/10 MiB\n\
+///      unpack   |"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultiBar {
+    tasks: Vec<(TaskId, Task)>,
+    next: u64,
+}
+
+/// Identifies a task within a `MultiBar`
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub struct TaskId(u64);
+
+impl MultiBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a task displayed with a `Bar`
+    pub fn add_bar(&mut self, label: &str) -> TaskId {
+        self.add(label, Indicator::Bar(Bar::new()))
+    }
+
+    /// Append a task displayed with a spinner of the given style
+    pub fn add_spinner(&mut self, label: &str, style: SpinnerStyle) -> TaskId {
+        self.add(label, Indicator::Spinner(AnySpinner::new(style)))
+    }
+
+    fn add(&mut self, label: &str, indicator: Indicator) -> TaskId {
+        let id = TaskId(self.next);
+        self.next += 1;
+        self.tasks.push((
+            id,
+            Task {
+                label: label.into(),
+                indicator,
+                stats: String::new(),
+                finished: false,
+            },
+        ));
+        id
+    }
+
+    /// Remove a task, returning it if it was present
+    pub fn remove(&mut self, id: TaskId) -> Option<Task> {
+        let i = self.tasks.iter().position(|&(x, _)| x == id)?;
+        Some(self.tasks.remove(i).1)
+    }
+
+    /// Remove all finished tasks
+    pub fn clear_finished(&mut self) {
+        self.tasks.retain(|x| !x.1.finished);
+    }
+
+    pub fn get(&self, id: TaskId) -> Option<&Task> {
+        self.tasks.iter().find(|&&(x, _)| x == id).map(|x| &x.1)
+    }
+
+    pub fn get_mut(&mut self, id: TaskId) -> Option<&mut Task> {
+        self.tasks.iter_mut().find(|x| x.0 == id).map(|x| &mut x.1)
+    }
+
+    /// Advance the spinners of all unfinished tasks `count` times
+    pub fn step(&mut self, count: u32) {
+        for &mut (_, ref mut task) in &mut self.tasks {
+            if let Indicator::Spinner(ref mut spinner) = task.indicator {
+                if !task.finished {
+                    spinner.step(count);
+                }
+            }
+        }
+    }
+
+    /// Number of tasks, and hence of lines rendered
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Whether every task has finished
+    pub fn is_finished(&self) -> bool {
+        self.tasks.iter().all(|x| x.1.finished)
+    }
+
+    /// Render each task as a line of at most `width` cells
+    pub fn lines(&self, width: usize) -> impl Iterator<Item = String> + '_ {
+        let label_width = self
+            .tasks
+            .iter()
+            .map(|x| width::str_width(&x.1.label))
+            .max()
+            .unwrap_or(0);
+        self.tasks
+            .iter()
+            .map(move |x| x.1.render(label_width, width))
+    }
+
+    /// Render all tasks at `width` cells, separated by newlines
+    ///
+    /// There's no trailing newline, so the cursor is left at the end of the last line.
+    pub fn render(&self, width: usize) -> String {
+        let mut out = String::new();
+        for (i, line) in self.lines(width).enumerate() {
+            if i != 0 {
+                out.push('\n');
+            }
+            out.push_str(&line);
+        }
+        out
+    }
+}
+
+/// A line of a `MultiBar`
+#[derive(Debug, Clone)]
+pub struct Task {
+    label: String,
+    indicator: Indicator,
+    stats: String,
+    finished: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Indicator {
+    Bar(Bar),
+    Spinner(AnySpinner),
+}
+
+impl Task {
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: &str) {
+        self.label.clear();
+        self.label.push_str(label);
+    }
+
+    pub fn stats(&self) -> &str {
+        &self.stats
+    }
+
+    /// Replace the text displayed after the indicator, e.g. a rate or ETA
+    pub fn set_stats(&mut self, stats: impl Display) {
+        self.stats.clear();
+        // Writing to a `String` can't fail
+        let _ = fmt::write(&mut self.stats, format_args!("{}", stats));
+    }
+
+    /// Mark the task finished, filling its bar or stopping its spinner
+    pub fn finish(&mut self) {
+        self.finished = true;
+        if let Indicator::Bar(ref mut bar) = self.indicator {
+            bar.set(1.0);
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn render(&self, label_width: usize, width: usize) -> String {
+        let mut label = self.label.clone();
+        for _ in width::str_width(&label)..label_width {
+            label.push(' ');
+        }
+        let mut row = Row::new().push(Item::Fixed(&label)).push(Item::Gap(1));
+        row = match self.indicator {
+            Indicator::Bar(ref bar) => row
+                .push(Item::Fixed(&"["))
+                .push(Item::Flex(bar, 1))
+                .push(Item::Fixed(&"]")),
+            Indicator::Spinner(ref spinner) => row.push(Item::Fixed(spinner)),
+        };
+        if !self.stats.is_empty() {
+            row = row.push(Item::Gap(1)).push(Item::Fixed(&self.stats));
+        }
+        row.render(width)
+    }
+}
+
+/// Tasks set their bar's progress directly
+impl Progress for Task {
+    fn set(&mut self, value: f32) {
+        if let Indicator::Bar(ref mut bar) = self.indicator {
+            bar.set(value);
+        }
+    }
+}
+
+impl Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label_width = width::str_width(&self.label);
+        f.write_str(&self.render(label_width, f.width().unwrap_or(80)))
+    }
+}
+
+/// A set of tasks measured in a common unit, rendered one line per task followed by a line summarizing them all
+///
+/// The summary shows overall progress, the combined rate, and the time remaining at that rate, and is kept up to date
+/// as members progress.
+///
+/// # Examples
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use yapb::multi::TaskGroup;
+/// let start = Instant::now();
+/// let mut group = TaskGroup::new("B", 1.0, Duration::from_millis(100));
+/// let a = group.add("a.iso", 2 << 20);
+/// let b = group.add("b.iso", 1 << 20);
+/// group.record(start, a, 0);
+/// let later = start + Duration::from_secs(1);
+/// group.record(later, a, 1 << 20);
+/// group.record(later, b, 1 << 20);
+/// assert_eq!(
+///     group.render(40),
+///     "a.iso [██████      ] 1.00 MiB/s\n\
+///      b.iso [████████████]\n\
+///      total [████████    ] 1.00 MiB/s, 1s left"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskGroup<'a> {
+    unit: &'a str,
+    alpha: f32,
+    interval: Duration,
+    members: Vec<(TaskId, Member)>,
+    next: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    label: String,
+    done: u64,
+    total: u64,
+    throughput: Throughput,
+}
+
+impl Member {
+    fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.done as f64 / self.total as f64) as f32
+        }
+    }
+}
+
+impl<'a> TaskGroup<'a> {
+    /// Track tasks measured in `unit`s, smoothing rates as described by `Throughput::new`
+    pub fn new(unit: &'a str, alpha: f32, interval: Duration) -> Self {
+        Self {
+            unit,
+            alpha,
+            interval,
+            members: Vec::new(),
+            next: 0,
+        }
+    }
+
+    /// Append a task that's complete after `total` units
+    pub fn add(&mut self, label: &str, total: u64) -> TaskId {
+        let id = TaskId(self.next);
+        self.next += 1;
+        self.members.push((
+            id,
+            Member {
+                label: label.into(),
+                done: 0,
+                total,
+                throughput: Throughput::new(self.alpha, self.interval),
+            },
+        ));
+        id
+    }
+
+    /// Remove a task, returning whether it was present
+    ///
+    /// Its progress no longer counts towards the summary.
+    pub fn remove(&mut self, id: TaskId) -> bool {
+        let len = self.members.len();
+        self.members.retain(|x| x.0 != id);
+        self.members.len() != len
+    }
+
+    /// Record that task `id` has completed `count` additional units as of `now`
+    pub fn record(&mut self, now: Instant, id: TaskId, count: u64) {
+        if let Some(member) = self.members.iter_mut().find(|x| x.0 == id) {
+            let member = &mut member.1;
+            let count = count.min(member.total - member.done);
+            member.done += count;
+            member.throughput.record(now, count);
+        }
+    }
+
+    /// Record that no tasks have progressed as of `now`
+    pub fn tick(&mut self, now: Instant) {
+        for member in &mut self.members {
+            member.1.throughput.tick(now);
+        }
+    }
+
+    /// Fraction of all units completed
+    pub fn fraction(&self) -> f32 {
+        let (done, total) = self.totals();
+        if total == 0 {
+            1.0
+        } else {
+            (done as f64 / total as f64) as f32
+        }
+    }
+
+    /// Combined smoothed rate of unfinished tasks in units per second
+    pub fn rate(&self) -> f64 {
+        self.members
+            .iter()
+            .filter(|x| x.1.done < x.1.total)
+            .map(|x| x.1.throughput.get())
+            .sum()
+    }
+
+    /// Time until every task is complete at the current combined rate, if any progress has been made
+    pub fn remaining(&self) -> Option<Duration> {
+        let (done, total) = self.totals();
+        let rate = self.rate();
+        if rate <= 0.0 {
+            return None;
+        }
+        // A decayed rate can make the estimate too large to represent
+        Duration::try_from_secs_f64((total - done) as f64 / rate).ok()
+    }
+
+    fn totals(&self) -> (u64, u64) {
+        self.members.iter().fold((0, 0), |(done, total), x| {
+            (done + x.1.done, total + x.1.total)
+        })
+    }
+
+    /// Render one line per task and a summary line, each at most `width` cells, separated by newlines
+    ///
+    /// Labels, bars, and statistics are aligned in columns.
+    pub fn render(&self, width: usize) -> String {
+        let mut lines = Vec::with_capacity(self.members.len() + 1);
+        for (_, member) in &self.members {
+            let rate = member.throughput.get();
+            let stats = if rate > 0.0 && member.done < member.total {
+                Rate(rate, self.unit).to_string()
+            } else {
+                String::new()
+            };
+            lines.push((&member.label[..], member.fraction(), stats));
+        }
+        let mut stats = String::new();
+        if self.rate() > 0.0 {
+            stats = Rate(self.rate(), self.unit).to_string();
+        }
+        if let Some(remaining) = self.remaining() {
+            stats = format!("{}, {} left", stats, Human(remaining));
+        }
+        lines.push((SUMMARY, self.fraction(), stats));
+
+        // Columns pad every label and every statistic to a common width, so that all bars get the same share of the
+        // line
+        let (mut labels, mut stats) = (Columns::new(), Columns::new());
+        for line in &lines {
+            labels.push(&[&line.0]);
+            stats.push(&[&line.2]);
+        }
+        let has_stats = lines.iter().any(|x| !x.2.is_empty());
+        let (labels, stats) = (labels.lines(), stats.lines());
+        let mut out = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let mut bar = Bar::new();
+            bar.set(line.1);
+            let mut row = Row::new()
+                .push(Item::Fixed(&labels[i]))
+                .push(Item::Gap(1))
+                .push(Item::Fixed(&"["))
+                .push(Item::Flex(&bar, 1))
+                .push(Item::Fixed(&"]"));
+            if has_stats {
+                row = row.push(Item::Gap(1)).push(Item::Fixed(&stats[i]));
+            }
+            // Omit the padding of shorter statistics
+            out.push(row.render(width).trim_end().to_string());
+        }
+        out.join("\n")
+    }
+}
+
+/// Label of a `TaskGroup`'s summary line
+const SUMMARY: &str = "total";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifecycle() {
+        let mut tasks = MultiBar::new();
+        let a = tasks.add_bar("a");
+        let b = tasks.add_spinner("bb", SpinnerStyle::Line);
+        tasks.step(1);
+        assert_eq!(tasks.render(10), "a  [     ]\nbb /");
+        tasks.get_mut(a).unwrap().finish();
+        tasks.get_mut(b).unwrap().finish();
+        tasks.step(1);
+        assert_eq!(tasks.render(10), "a  [█████]\nbb /");
+        assert!(tasks.is_finished());
+        tasks.remove(b).unwrap();
+        assert!(tasks.remove(b).is_none());
+        assert_eq!(tasks.lines(6).collect::<Vec<_>>(), ["a [██]"]);
+        tasks.clear_finished();
+        assert!(tasks.is_empty());
+        assert_eq!(tasks.render(10), "");
+    }
+
+    #[test]
+    fn group() {
+        let start = Instant::now();
+        let mut group = TaskGroup::new("req", 1.0, Duration::from_secs(1));
+        assert_eq!(group.render(12), "total [████]");
+        let a = group.add("a", 10);
+        let b = group.add("b", 10);
+        group.record(start, a, 0);
+        group.record(start + Duration::from_secs(1), a, 5);
+        assert_eq!(group.fraction(), 0.25);
+        assert_eq!(group.remaining(), Some(Duration::from_secs(3)));
+        assert!(group.remove(b));
+        assert!(!group.remove(b));
+        assert_eq!(group.remaining(), Some(Duration::from_secs(1)));
+        assert_eq!(group.render(8), "a     []\ntotal []");
+        let c = group.add("進捗", 10);
+        group.record(start + Duration::from_secs(2), c, 10);
+        assert_eq!(
+            group.render(36),
+            "a     [████    ] 5.00 req/s\n\
+             進捗  [████████]\n\
+             total [██████  ] 5.00 req/s, 1s left"
+        );
+    }
+
+    #[test]
+    fn group_stalled() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let mut group = TaskGroup::new("B", 0.5, interval);
+        let a = group.add("a", 100);
+        group.record(start, a, 0);
+        group.record(start + interval, a, 10);
+        for i in 2..600 {
+            group.tick(start + interval * i);
+            group.render(60);
+        }
+        assert_eq!(group.remaining(), None);
+    }
+}
@@ -0,0 +1,199 @@
+//! Operating system command escape sequences that report progress outside the terminal's text area
+
+use std::fmt::{self, Display, Write};
+
+use {Progress, WidthHint};
+
+/// Progress shown in the OS taskbar or terminal tab, via the `OSC 9;4` sequence from ConEmu and Windows Terminal
+///
+/// Write this to the terminal alongside an in-terminal indicator whenever progress changes, and write it with state
+/// `TaskbarState::Hidden` when finished. Terminals that don't support the sequence ignore it.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::osc::{Taskbar, TaskbarState};
+/// let mut taskbar = Taskbar::new();
+/// taskbar.set(0.42);
+/// assert_eq!(format!("{}", taskbar), "\x1b]9;4;1;42\x07");
+/// taskbar.set_state(TaskbarState::Hidden);
+/// assert_eq!(format!("{}", taskbar), "\x1b]9;4;0;0\x07");
+/// ```
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone, Default)]
+pub struct Taskbar {
+    /// Progress in percent, in [0, 100]
+    percent: u8,
+    state: TaskbarState,
+}
+
+impl Taskbar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Progress in percent
+    pub fn get(&self) -> u8 {
+        self.percent
+    }
+
+    pub fn state(&self) -> TaskbarState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: TaskbarState) {
+        self.state = state;
+    }
+}
+
+impl Progress for Taskbar {
+    fn set(&mut self, value: f32) {
+        // NaN saturates to 0
+        self.percent = (value.clamp(0.0, 1.0) * 100.0).floor() as u8;
+    }
+}
+
+impl Display for Taskbar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = match self.state {
+            TaskbarState::Hidden | TaskbarState::Indeterminate => 0,
+            _ => self.percent,
+        };
+        write!(f, "\x1b]9;4;{};{}\x07", self.state as u8, percent)
+    }
+}
+
+/// Escape sequences occupy no cells
+impl WidthHint for Taskbar {
+    fn width_hint(&self, _: Option<usize>) -> usize {
+        0
+    }
+}
+
+/// How a `Taskbar` is presented
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone, Default)]
+pub enum TaskbarState {
+    /// Remove the progress indicator
+    Hidden = 0,
+    #[default]
+    Normal = 1,
+    /// Show progress as failed, typically in red
+    Error = 2,
+    /// Show activity without a known proportion of progress
+    Indeterminate = 3,
+    /// Show progress as suspended, typically in yellow
+    Paused = 4,
+}
+
+/// Progress shown in the terminal's window title, e.g. `[42%] building`, so background tabs show it at a glance
+///
+/// The title is set with `OSC 2`, or `OSC 0` to also set the icon name. Control characters in the label are
+/// dropped so they can't terminate the sequence early.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::osc::Title;
+/// let mut title = Title::new("building");
+/// title.set(0.42);
+/// assert_eq!(format!("{}", title), "\x1b]2;[42%] building\x07");
+/// title.set_icon(true);
+/// title.clear();
+/// assert_eq!(format!("{}", title), "\x1b]0;building\x07");
+/// ```
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub struct Title<'a> {
+    /// Progress in percent, in [0, 100], if any
+    percent: Option<u8>,
+    label: &'a str,
+    icon: bool,
+}
+
+impl<'a> Title<'a> {
+    pub fn new(label: &'a str) -> Self {
+        Self {
+            percent: None,
+            label,
+            icon: false,
+        }
+    }
+
+    /// Progress in percent, if set
+    pub fn get(&self) -> Option<u8> {
+        self.percent
+    }
+
+    /// Omit the percentage, e.g. when progress is indeterminate
+    pub fn clear(&mut self) {
+        self.percent = None;
+    }
+
+    pub fn label(&self) -> &'a str {
+        self.label
+    }
+
+    pub fn set_label(&mut self, label: &'a str) {
+        self.label = label;
+    }
+
+    /// Whether to also set the icon name, which some terminals show in place of the title
+    pub fn set_icon(&mut self, icon: bool) {
+        self.icon = icon;
+    }
+}
+
+impl<'a> Progress for Title<'a> {
+    fn set(&mut self, value: f32) {
+        // NaN saturates to 0
+        self.percent = Some((value.clamp(0.0, 1.0) * 100.0).floor() as u8);
+    }
+}
+
+impl<'a> Display for Title<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b]{};", if self.icon { 0 } else { 2 })?;
+        if let Some(percent) = self.percent {
+            write!(f, "[{}%]", percent)?;
+            if !self.label.is_empty() {
+                f.write_char(' ')?;
+            }
+        }
+        for c in self.label.chars().filter(|c| !c.is_control()) {
+            f.write_char(c)?;
+        }
+        f.write_char('\x07')
+    }
+}
+
+impl<'a> WidthHint for Title<'a> {
+    fn width_hint(&self, _: Option<usize>) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn taskbar() {
+        let mut taskbar = Taskbar::new();
+        assert_eq!(taskbar.to_string(), "\x1b]9;4;1;0\x07");
+        taskbar.set(1.0);
+        assert_eq!(taskbar.to_string(), "\x1b]9;4;1;100\x07");
+        taskbar.set(0.999);
+        assert_eq!(taskbar.get(), 99);
+        taskbar.set_state(TaskbarState::Error);
+        assert_eq!(taskbar.to_string(), "\x1b]9;4;2;99\x07");
+        taskbar.set_state(TaskbarState::Indeterminate);
+        assert_eq!(taskbar.to_string(), "\x1b]9;4;3;0\x07");
+    }
+
+    #[test]
+    fn title() {
+        let mut title = Title::new("a\x07b\x1b\\c");
+        assert_eq!(title.to_string(), "\x1b]2;ab\\c\x07");
+        title.set(1.0);
+        title.set_label("");
+        assert_eq!(title.to_string(), "\x1b]2;[100%]\x07");
+    }
+}
@@ -0,0 +1,154 @@
+//! Log-style progress output for non-interactive destinations
+//!
+//! Animated indicators rely on carriage returns or cursor movement, which make a mess of CI logs and files. A
+//! `PlainReporter` instead yields a complete line at milestones, e.g. every 10% or every 30 seconds, which can be
+//! printed with `println!` or passed to a logger.
+
+use std::time::{Duration, Instant};
+
+use duration::Human;
+
+/// Produces a line of output each time progress crosses a milestone
+///
+/// A line is produced on the first update, whenever progress reaches the next multiple of the step, and whenever the
+/// interval has passed since the previous line.
+///
+/// # Examples
+/// ```
+/// # use yapb::plain::PlainReporter;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut reporter = PlainReporter::new().with_step(0.25);
+/// assert_eq!(reporter.update(start, 0.0, "copying").unwrap(), "0% copying (0ms)");
+/// assert_eq!(reporter.update(start + Duration::from_secs(1), 0.1, "copying"), None);
+/// assert_eq!(reporter.update(start + Duration::from_secs(3), 0.3, "copying").unwrap(), "30% copying (3s)");
+/// assert_eq!(reporter.finish(start + Duration::from_secs(9), "copied"), "100% copied (9s)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlainReporter {
+    step: f32,
+    interval: Duration,
+    start: Option<Instant>,
+    last: Option<Instant>,
+    milestone: u32,
+}
+
+impl PlainReporter {
+    /// Report every 10% and every 30 seconds
+    pub fn new() -> Self {
+        Self {
+            step: 0.1,
+            interval: Duration::from_secs(30),
+            start: None,
+            last: None,
+            milestone: 0,
+        }
+    }
+
+    /// Report each time progress advances by `step`, a fraction of the total
+    ///
+    /// A step of zero or less disables progress-based milestones.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Report whenever `interval` has passed without another report
+    ///
+    /// A zero interval disables time-based milestones.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Record that progress has reached `fraction` at `now`, returning a line if a milestone was reached
+    pub fn update(&mut self, now: Instant, fraction: f32, message: &str) -> Option<String> {
+        let start = *self.start.get_or_insert(now);
+        let milestone = self.milestone(fraction);
+        let due = match self.last {
+            None => true,
+            Some(last) => {
+                milestone > self.milestone
+                    || (self.interval != Duration::from_secs(0)
+                        && now.saturating_duration_since(last) >= self.interval)
+            }
+        };
+        if !due {
+            return None;
+        }
+        self.last = Some(now);
+        self.milestone = self.milestone.max(milestone);
+        Some(line(
+            now.saturating_duration_since(start),
+            fraction,
+            message,
+        ))
+    }
+
+    /// Produce a final line reporting completion at `now`
+    pub fn finish(&mut self, now: Instant, message: &str) -> String {
+        let start = *self.start.get_or_insert(now);
+        self.last = Some(now);
+        line(now.saturating_duration_since(start), 1.0, message)
+    }
+
+    /// Index of the most recent milestone at or below `fraction`
+    fn milestone(&self, fraction: f32) -> u32 {
+        if self.step <= 0.0 || !fraction.is_finite() {
+            return 0;
+        }
+        // Tolerate rounding error so that e.g. 0.3 counts as the third 0.1 step
+        (fraction.clamp(0.0, 1.0) / self.step + 1e-4).floor() as u32
+    }
+}
+
+impl Default for PlainReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn line(elapsed: Duration, fraction: f32, message: &str) -> String {
+    // Truncate so 100% is only reported on completion, tolerating rounding error in e.g. 0.15
+    let percent = (fraction.clamp(0.0, 1.0) * 100.0 + 1e-3).floor();
+    if message.is_empty() {
+        format!("{}% ({})", percent, Human(elapsed))
+    } else {
+        format!("{}% {} ({})", percent, message, Human(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestones() {
+        let start = Instant::now();
+        let mut reporter = PlainReporter::new();
+        let lines = (0..=20)
+            .filter_map(|i| reporter.update(start + Duration::from_secs(i), i as f32 / 20.0, ""))
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[3], "30% (6s)");
+        assert_eq!(lines[10], "100% (20s)");
+    }
+
+    #[test]
+    fn interval() {
+        let start = Instant::now();
+        let mut reporter = PlainReporter::new().with_step(0.0);
+        let lines = (0..=100)
+            .filter_map(|i| reporter.update(start + Duration::from_secs(i), 0.005 * i as f32, "x"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "0% x (0ms)",
+                "15% x (30s)",
+                "30% x (1m 0s)",
+                "45% x (1m 30s)"
+            ]
+        );
+    }
+}
@@ -1,80 +1,291 @@
 //! Helpers to display compact human-readable numbers
 
+use std::error::Error;
 use std::fmt::{self, Display, Write};
 
+use width;
+
+const BINARY: [&str; 9] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
+const SI_SMALL: [&str; 10] = ["m", "µ", "n", "p", "f", "a", "z", "y", "r", "q"];
+const SI_LARGE: [&str; 11] = ["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+/// Write `x` honoring the width, fill, and alignment of `f`
+///
+/// `x` is formatted without a width, but with any precision, so `Display` impls may call this on themselves when a
+/// width is requested.
+fn padded<T: Display + ?Sized>(f: &mut fmt::Formatter, x: &T) -> fmt::Result {
+    let text = match f.precision() {
+        Some(precision) => format!("{:.*}", precision, x),
+        None => x.to_string(),
+    };
+    width::pad(f, &text)
+}
+
 /// Find the smallest binary prefix with which the whole part of `x` is at most three digits, and
 /// return the scaled `x` and that prefix.
 pub fn binary(x: f64) -> (f64, &'static str) {
-    const TABLE: [&str; 9] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
-
-    let mut i = 0;
-    let mut scaled = x;
-    while scaled.abs() >= 1000.0 && i < TABLE.len() - 1 {
-        i += 1;
-        scaled /= 1024.0;
-    }
-    (scaled, TABLE[i])
+    Scale::BINARY.apply(x)
 }
 
 /// Given an exact value `x`, return the same value scaled to the nearest lesser SI prefix, and the prefix in question.
 pub fn si(x: f64) -> (f64, &'static str) {
-    const SMALL: [&'static str; 8] = ["m", "µ", "n", "p", "f", "a", "z", "y"];
-    const LARGE: [&'static str; 8] = ["k", "M", "G", "T", "P", "E", "Z", "Y"];
-
-    if x.abs() < 1.0 {
-        let mut divisor = 1e-3;
-        let (last, most) = SMALL.split_last().unwrap();
-        for prefix in most {
-            let next = divisor * 1e-3;
-            if next < x.abs() {
-                return (x / divisor, prefix);
+    Scale::SI.apply(x)
+}
+
+/// A system of prefixes or units separated by a constant factor, generalizing `binary` and `si`
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::{Scale, Scaled};
+/// // Durations in seconds, expressed in the largest unit not exceeding them
+/// const TIME: Scale = Scale {
+///     base: 60.0,
+///     limit: 60.0,
+///     large: &["s", "min", "h"],
+///     small: &[],
+/// };
+/// assert_eq!(TIME.apply(5400.0), (1.5, "h"));
+/// assert_eq!(format!("{}", Scaled(90.0, &TIME)), "1.50 min");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Scale<'a> {
+    /// Factor between successive prefixes
+    pub base: f64,
+    /// Magnitude at or beyond which the next larger prefix is used
+    ///
+    /// Typically equal to `base`, but may be smaller to bound the number of digits, as `BINARY` does.
+    pub limit: f64,
+    /// Prefixes for increasing powers of `base`, starting from the zeroth
+    ///
+    /// If empty, magnitudes of at least 1 are left unscaled, as if the table held only the empty prefix.
+    pub large: &'a [&'a str],
+    /// Prefixes for `base` to the power of -1, -2, etc., used for magnitudes less than 1
+    pub small: &'a [&'a str],
+}
+
+impl Scale<'static> {
+    /// IEC binary prefixes, switching at 1000 so the whole part never exceeds three digits
+    pub const BINARY: Self = Scale {
+        base: 1024.0,
+        limit: 1000.0,
+        large: &BINARY,
+        small: &[],
+    };
+
+    /// SI prefixes
+    pub const SI: Self = Scale {
+        base: 1000.0,
+        limit: 1000.0,
+        large: &SI_LARGE,
+        small: &SI_SMALL,
+    };
+}
+
+impl<'a> Scale<'a> {
+    /// Scale `x` by the largest prefix that leaves its magnitude under `limit`, or for magnitudes under 1, by the
+    /// smallest prefix that leaves it at least 1, and return the scaled `x` and that prefix
+    ///
+    /// Values beyond the range of the tables are scaled by the most extreme prefix available.
+    pub fn apply(&self, x: f64) -> (f64, &'a str) {
+        // Zero is smaller than every prefix, but reads best without one
+        if x.abs() < 1.0 && x != 0.0 && !self.small.is_empty() {
+            let scaled = |i: usize| x / self.base.powi(-(i as i32 + 1));
+            // Tolerate rounding error in the powers of `base`, which could otherwise select the next smaller prefix
+            // for exact powers such as 1e-24
+            let mut i = 0;
+            while i < self.small.len() - 1 && scaled(i).abs() < 1.0 - 1e-9 {
+                i += 1;
             }
-            divisor = next;
+            return (scaled(i), self.small[i]);
         }
-        (x / divisor, last)
-    } else if x.abs() < 1e3 {
-        (x, "")
-    } else {
-        let mut divisor = 1e3;
-        let (last, most) = LARGE.split_last().unwrap();
-        for prefix in most {
-            let next = divisor * 1e3;
-            if next > x.abs() {
-                return (x / divisor, prefix);
-            }
-            divisor = next;
+        if self.large.is_empty() {
+            return (x, "");
+        }
+        let mut i = 0;
+        while i < self.large.len() - 1 && x.abs() >= self.limit * self.base.powi(i as i32) {
+            i += 1;
         }
-        (x / divisor, last)
+        (x / self.base.powi(i as i32), self.large[i])
+    }
+}
+
+/// Helper struct to compactly format a value scaled by a custom `Scale`, with 3 significant figures
+#[derive(Debug, Copy, Clone)]
+pub struct Scaled<'a>(pub f64, pub &'a Scale<'a>);
+impl<'a> Localize for Scaled<'a> {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        let (value, prefix) = self.1.apply(self.0);
+        sigfigs(f, separators, value, 3)?;
+        f.write_char(' ')?;
+        f.write_str(prefix)
+    }
+}
+
+/// Parse a quantity of `unit`s with an optional binary prefix, e.g. `12.1 KiB`, as produced by `Binary`
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::parse_binary;
+/// assert_eq!(parse_binary("12 KiB", "B"), Ok(12.0 * 1024.0));
+/// assert_eq!(parse_binary("999 B", "B"), Ok(999.0));
+/// assert!(parse_binary("12 kB", "B").is_err());
+/// ```
+pub fn parse_binary(s: &str, unit: &str) -> Result<f64, ParsePrefixError> {
+    let (value, prefix) = split_prefixed(s, unit)?;
+    let i = BINARY
+        .iter()
+        .position(|&x| x == prefix)
+        .ok_or(ParsePrefixError(()))?;
+    Ok(value * 1024f64.powi(i as i32))
+}
+
+/// Parse a quantity of `unit`s with an optional SI prefix, e.g. `1.20 kreq`, as produced by `Scientific`
+///
+/// `u` is accepted in place of `µ`.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::parse_si;
+/// assert_eq!(parse_si("1.5 kreq", "req"), Ok(1500.0));
+/// assert_eq!(parse_si("250ms", "s"), Ok(0.25));
+/// ```
+pub fn parse_si(s: &str, unit: &str) -> Result<f64, ParsePrefixError> {
+    let (value, prefix) = split_prefixed(s, unit)?;
+    let prefix = if prefix == "u" { "µ" } else { prefix };
+    if let Some(i) = SI_LARGE.iter().position(|&x| x == prefix) {
+        return Ok(value * 10f64.powi(3 * i as i32));
+    }
+    if let Some(i) = SI_SMALL.iter().position(|&x| x == prefix) {
+        return Ok(value / 10f64.powi(3 * (i as i32 + 1)));
+    }
+    Err(ParsePrefixError(()))
+}
+
+/// Split `s` into its leading number and the prefix preceding `unit`
+fn split_prefixed<'a>(s: &'a str, unit: &str) -> Result<(f64, &'a str), ParsePrefixError> {
+    let s = s.trim();
+    let rest = s.strip_suffix(unit).ok_or(ParsePrefixError(()))?;
+    // The longest leading number wins, so an exponent isn't mistaken for the exa prefix
+    let (value, prefix) = rest
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(Some(rest.len()))
+        .rev()
+        .filter_map(|i| {
+            rest[..i]
+                .trim_end()
+                .parse::<f64>()
+                .ok()
+                .map(|x| (x, &rest[i..]))
+        })
+        .next()
+        .ok_or(ParsePrefixError(()))?;
+    Ok((value, prefix.trim_start()))
+}
+
+/// Error returned when parsing a quantity that isn't a number followed by a known prefix and the expected unit
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ParsePrefixError(());
+
+impl Display for ParsePrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("expected a number followed by an optional prefix and the unit")
     }
 }
 
+impl Error for ParsePrefixError {}
+
 /// Format `value` compactly with exactly `figures` significant figures
 ///
 /// For compactness, exponential notation is used for values that are larger than `1eN` or smaller than `1e-N`.
 pub fn fmt_sigfigs(f: &mut fmt::Formatter, value: f64, figures: usize) -> fmt::Result {
+    sigfigs(f, Separators::ENGLISH, value, figures)
+}
+
+fn sigfigs(
+    f: &mut fmt::Formatter,
+    separators: Separators,
+    value: f64,
+    figures: usize,
+) -> fmt::Result {
     if value == 0.0 {
-        return write!(f, "{:.*}", figures - 1, 0.0);
+        return number(f, separators, format_args!("{:.*}", figures - 1, 0.0));
     }
     let log = value.abs().log10() as isize;
     if log < 0 || log >= figures as isize {
-        write!(f, "{:.*e}", figures - 1, value)
+        number(f, separators, format_args!("{:.*e}", figures - 1, value))
     } else {
-        write!(f, "{:.*}", figures - (log + 1) as usize, value)
+        number(
+            f,
+            separators,
+            format_args!("{:.*}", figures - (log + 1) as usize, value),
+        )
     }
 }
 
 /// Helper struct to format a float with `format_sigfigs`
 #[derive(Debug, Copy, Clone)]
 pub struct SigFigs(pub f64, pub usize);
-impl Display for SigFigs {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt_sigfigs(f, self.0, self.1)
+impl Localize for SigFigs {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        sigfigs(f, separators, self.0, self.1)
+    }
+}
+
+/// Format `value` with `figures` significant figures without ever using exponential notation
+///
+/// Large values are rounded to `figures` significant figures and padded with zeros, and small values are written with
+/// as many leading zeros as needed.
+pub fn fmt_sigfigs_plain(f: &mut fmt::Formatter, value: f64, figures: usize) -> fmt::Result {
+    sigfigs_plain(f, Separators::ENGLISH, value, figures)
+}
+
+fn sigfigs_plain(
+    f: &mut fmt::Formatter,
+    separators: Separators,
+    value: f64,
+    figures: usize,
+) -> fmt::Result {
+    if value == 0.0 || !value.is_finite() {
+        return number(f, separators, format_args!("{:.*}", figures - 1, value));
+    }
+    let magnitude = |x: f64| x.abs().log10().floor() as i32;
+    let round = |log: i32| {
+        let factor = 10f64.powi(figures as i32 - 1 - log);
+        (value * factor).round() / factor
+    };
+    let mut log = magnitude(value);
+    let mut rounded = round(log);
+    // Rounding may carry into a new digit, e.g. 9.996 to 10.0
+    if magnitude(rounded) > log {
+        log += 1;
+        rounded = round(log);
+    }
+    let decimals = (figures as i32 - 1 - log).max(0) as usize;
+    number(f, separators, format_args!("{:.*}", decimals, rounded))
+}
+
+/// Helper struct to format a float with `fmt_sigfigs_plain`
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::PlainSigFigs;
+/// assert_eq!(PlainSigFigs(123456.0, 3).to_string(), "123000");
+/// assert_eq!(PlainSigFigs(0.000123456, 3).to_string(), "0.000123");
+/// assert_eq!(PlainSigFigs(9.996, 3).to_string(), "10.0");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct PlainSigFigs(pub f64, pub usize);
+impl Localize for PlainSigFigs {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        sigfigs_plain(f, separators, self.0, self.1)
     }
 }
 
 /// Helper struct to compactly format a value with a binary unit prefix
 ///
-/// If the provided value is equal to 0 or is in [1e-2, 1e28), this will produce at most 7 ASCII characters.
+/// If the provided value is equal to 0 or is in [1e-2, 1.2e27), this will produce at most 7 ASCII characters. Beyond
+/// 999 Yi, the largest prefix, the scaled value is written with an exponent.
 ///
 /// # Examples
 /// ```
@@ -82,13 +293,14 @@ impl Display for SigFigs {
 /// ```
 #[derive(Debug, Copy, Clone)]
 pub struct Binary(pub f64);
-impl Display for Binary {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Localize for Binary {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
         if self.0 < 1.0 && self.0 >= 1e-2 {
-            write!(f, "{:.2} ", self.0)?;
+            number(f, separators, format_args!("{:.2}", self.0))?;
+            f.write_char(' ')?;
         } else {
             let (value, prefix) = binary(self.0);
-            fmt_sigfigs(f, value, 3)?;
+            sigfigs(f, separators, value, 3)?;
             f.write_char(' ')?;
             f.write_str(prefix)?;
         }
@@ -98,19 +310,441 @@ impl Display for Binary {
 
 /// Helper struct to compactly format a value with a SI unit prefix
 ///
-/// If the provided value is in [1e-24, 1e28), this will produce at most 6 ASCII characters.
+/// If the provided value is in [1e-30, 1e33), this will produce at most 6 characters, all ASCII except `µ`.
 #[derive(Debug, Copy, Clone)]
 pub struct Scientific(pub f64);
-impl Display for Scientific {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Localize for Scientific {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
         let (value, prefix) = si(self.0);
-        fmt_sigfigs(f, value, 3)?;
+        sigfigs(f, separators, value, 3)?;
         f.write_char(' ')?;
         f.write_str(prefix)?;
         Ok(())
     }
 }
 
+/// Like `Binary`, but padded on the left to exactly 7 characters, so columns don't shift as values cross prefix
+/// boundaries
+///
+/// Values beyond 999 Yi are written to one significant figure, e.g. `4e3 Yi`, which fits up to 1e33. Smaller positive
+/// values outside the range documented for `Binary`, or negative values, may produce more.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::FixedBinary;
+/// assert_eq!(format!("{}B/s", FixedBinary(999.0)), "   999 B/s");
+/// assert_eq!(format!("{}B/s", FixedBinary(12345.0)), "12.1 KiB/s");
+/// assert_eq!(format!("{}B/s", FixedBinary(5e27)), " 4e3 YiB/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct FixedBinary(pub f64);
+impl Localize for FixedBinary {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        let (value, prefix) = binary(self.0);
+        // `Binary` would need an exponent and three figures here, so keep only one
+        let text = if prefix == BINARY[BINARY.len() - 1] && value.abs() >= 999.5 {
+            format!("{} {}", Localized(SigFigs(value, 1), separators), prefix)
+        } else {
+            Localized(Binary(self.0), separators).to_string()
+        };
+        write!(f, "{:>7}", text)
+    }
+}
+
+/// Like `Scientific`, but padded on the left to exactly 6 characters, so columns don't shift as values cross prefix
+/// boundaries
+///
+/// Values outside the range documented for `Scientific`, or negative values, may produce more.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::FixedScientific;
+/// assert_eq!(format!("{}req/s", FixedScientific(42.0)), " 42.0 req/s");
+/// assert_eq!(format!("{}req/s", FixedScientific(1234.0)), "1.23 kreq/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct FixedScientific(pub f64);
+impl Localize for FixedScientific {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        write!(
+            f,
+            "{:>6}",
+            Localized(Scientific(self.0), separators).to_string()
+        )
+    }
+}
+
+/// Conventions for the prefixes of byte quantities
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum Convention {
+    /// Powers of 1024 with IEC prefixes, e.g. `KiB`
+    #[default]
+    Binary,
+    /// Powers of 1000 with SI prefixes, e.g. `kB`
+    Si,
+    /// Powers of 1024 with the traditional JEDEC prefixes, e.g. `KB`, as used by Windows
+    Jedec,
+}
+
+/// Helper struct to compactly format a number of bytes, including the `B` unit
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::{ByteSize, Convention};
+/// assert_eq!(format!("{}", ByteSize(12345.0, Convention::Binary)), "12.1 KiB");
+/// assert_eq!(format!("{}", ByteSize(12345.0, Convention::Si)), "12.3 kB");
+/// assert_eq!(format!("{}", ByteSize(12345.0, Convention::Jedec)), "12.1 KB");
+/// assert_eq!(format!("{}/s", ByteSize(512.0, Convention::Si)), "512 B/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ByteSize(pub f64, pub Convention);
+impl Localize for ByteSize {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        match self.1 {
+            Convention::Binary => Binary(self.0).fmt_with(f, separators)?,
+            Convention::Si if self.0.abs() >= 1.0 => Scientific(self.0).fmt_with(f, separators)?,
+            // Fractional bytes are rare enough that an SI prefix would be more confusing than helpful
+            Convention::Si => Binary(self.0).fmt_with(f, separators)?,
+            Convention::Jedec => {
+                let (value, prefix) = binary(self.0);
+                if prefix.is_empty() {
+                    Binary(self.0).fmt_with(f, separators)?;
+                } else {
+                    sigfigs(f, separators, value, 3)?;
+                    f.write_char(' ')?;
+                    f.write_str(&prefix[..1])?;
+                }
+            }
+        }
+        f.write_char('B')
+    }
+}
+
+/// Like `Binary`, but for integers, which are scaled without first being converted to floating point
+///
+/// Counts above 2^53 can't be represented exactly by an `f64`, which could otherwise cause values just below a prefix
+/// boundary to be rounded across it.
+///
+/// Unlike `Binary`, which switches prefix once the scaled value reaches 1000, the scaled value is rounded to three
+/// figures before the prefix is chosen, so e.g. 1023999 is written as `0.98 Mi` where `Binary` writes `1000 Ki`.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::ExactBinary;
+/// assert_eq!(format!("{}B", ExactBinary(12345)), "12.1 KiB");
+/// assert_eq!(format!("{}B", ExactBinary(u128::from(u64::MAX))), "16.0 EiB");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ExactBinary(pub u128);
+impl Localize for ExactBinary {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        let (value, i) = scale_int(self.0, 1024, BINARY.len());
+        sigfigs(f, separators, value, 3)?;
+        f.write_char(' ')?;
+        f.write_str(BINARY[i])
+    }
+}
+
+impl From<u64> for ExactBinary {
+    fn from(x: u64) -> Self {
+        ExactBinary(x.into())
+    }
+}
+
+/// Like `ByteSize`, but for integers, which are scaled without first being converted to floating point
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::{Convention, ExactByteSize};
+/// assert_eq!(format!("{}", ExactByteSize(12345, Convention::Si)), "12.3 kB");
+/// assert_eq!(format!("{}", ExactByteSize(999, Convention::Jedec)), "999 B");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ExactByteSize(pub u128, pub Convention);
+impl Localize for ExactByteSize {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        let (value, prefix) = match self.1 {
+            Convention::Binary => {
+                let (value, i) = scale_int(self.0, 1024, BINARY.len());
+                (value, BINARY[i])
+            }
+            Convention::Si => {
+                let (value, i) = scale_int(self.0, 1000, SI_LARGE.len());
+                (value, SI_LARGE[i])
+            }
+            Convention::Jedec => {
+                let (value, i) = scale_int(self.0, 1024, BINARY.len());
+                (value, &BINARY[i][..BINARY[i].len().min(1)])
+            }
+        };
+        sigfigs(f, separators, value, 3)?;
+        write!(f, " {}B", prefix)
+    }
+}
+
+/// Divide `x` by the smallest power of `base`, of less than `prefixes`, that leaves the quotient under 999.5, and
+/// return the quotient and that power
+///
+/// The quotient is compared after rounding to three digits, so e.g. 999.9 is never displayed as `1000`.
+fn scale_int(x: u128, base: u128, prefixes: usize) -> (f64, usize) {
+    let mut i = 0;
+    let mut divisor = 1;
+    let rounds_to_1000 = |divisor: u128| {
+        let (quotient, remainder) = (x / divisor, x % divisor);
+        quotient >= 1000 || (quotient == 999 && remainder * 2 >= divisor)
+    };
+    while rounds_to_1000(divisor) && i < prefixes - 1 {
+        divisor *= base;
+        i += 1;
+    }
+    let value = (x / divisor) as f64 + (x % divisor) as f64 / divisor as f64;
+    (value, i)
+}
+
+/// Helper struct to compactly format a rate in `unit`s per second, e.g. `4.52 MiB/s` or `1.20 kreq/s`
+///
+/// Binary prefixes are used when `unit` is `"B"`, and SI prefixes otherwise.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::Rate;
+/// assert_eq!(format!("{}", Rate(4.74e6, "B")), "4.52 MiB/s");
+/// assert_eq!(format!("{}", Rate(1200.0, "req")), "1.20 kreq/s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Rate<'a>(pub f64, pub &'a str);
+impl<'a> Localize for Rate<'a> {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        if self.1 == "B" {
+            Binary(self.0).fmt_with(f, separators)?;
+        } else {
+            Scientific(self.0).fmt_with(f, separators)?;
+        }
+        write!(f, "{}/s", self.1)
+    }
+}
+
+/// Helper struct to format an integer with its digits grouped in thousands by the given separator
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::Grouped;
+/// assert_eq!(format!("{}", Grouped(1234567, ',')), "1,234,567");
+/// assert_eq!(format!("{}", Grouped(1234567, ' ')), "1 234 567");
+/// assert_eq!(format!("{}", Grouped(999, ',')), "999");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Grouped(pub u64, pub char);
+impl Display for Grouped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.width().is_some() {
+            return padded(f, self);
+        }
+        self.fmt_with(
+            f,
+            Separators {
+                group: self.1,
+                ..Separators::ENGLISH
+            },
+        )
+    }
+}
+
+/// Uses the group separator of `separators` in place of the one given
+impl Localize for Grouped {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        let digits = self.0.to_string();
+        // Digits remaining before the next separator
+        let mut group = (digits.len() + 2) % 3 + 1;
+        for c in digits.chars() {
+            if group == 0 {
+                f.write_char(separators.group)?;
+                group = 3;
+            }
+            f.write_char(c)?;
+            group -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Helper struct to format a count in the style of social media, e.g. `890`, `1.2k`, or `34M`
+///
+/// The suffixes `k`, `M`, `B`, and `T` denote thousands, millions, billions, and trillions. At most one decimal place
+/// is shown, and only for values under 10 of the chosen suffix.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::Short;
+/// assert_eq!(format!("{}", Short(890.0)), "890");
+/// assert_eq!(format!("{}", Short(1234.0)), "1.2k");
+/// assert_eq!(format!("{}", Short(3.4e6)), "3.4M");
+/// assert_eq!(format!("{}", Short(56_789.0)), "57k");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Short(pub f64);
+impl Localize for Short {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        const SUFFIXES: [&str; 5] = ["", "k", "M", "B", "T"];
+
+        let mut i = 0;
+        let mut scaled = self.0;
+        // Compare against the rounded value, so e.g. 999 999 becomes 1.0M rather than 1000k
+        while scaled.abs() >= 999.5 && i < SUFFIXES.len() - 1 {
+            i += 1;
+            scaled /= 1000.0;
+        }
+        if i != 0 && scaled.abs() < 9.95 {
+            number(f, separators, format_args!("{:.1}", scaled))?;
+        } else {
+            number(f, separators, format_args!("{:.0}", scaled))?;
+        }
+        f.write_str(SUFFIXES[i])
+    }
+}
+
+/// Helper struct to format the ratio of two counts as a percentage, e.g. a cache hit rate
+///
+/// The formatter's precision sets the number of significant figures, defaulting to 3. If the denominator is zero,
+/// `n/a` is displayed.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::Ratio;
+/// assert_eq!(format!("{}", Ratio(1, 3)), "33.3%");
+/// assert_eq!(format!("{:.2}", Ratio(1, 3)), "33%");
+/// assert_eq!(format!("{}", Ratio(5, 0)), "n/a");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Ratio(pub u64, pub u64);
+impl Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        width::pad(f, &self.text(f, Separators::ENGLISH))
+    }
+}
+
+impl Localize for Ratio {
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result {
+        f.write_str(&self.text(f, separators))
+    }
+}
+
+impl Ratio {
+    fn text(&self, f: &fmt::Formatter, separators: Separators) -> String {
+        let figures = f.precision().unwrap_or(3).max(1);
+        if self.1 == 0 {
+            "n/a".to_string()
+        } else {
+            let percent = PlainSigFigs(100.0 * self.0 as f64 / self.1 as f64, figures);
+            format!("{}%", Localized(percent, separators))
+        }
+    }
+}
+
+/// Characters used to punctuate numbers
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Separators {
+    /// Separates the whole and fractional parts of a number
+    pub decimal: char,
+    /// Separates groups of thousands
+    pub group: char,
+}
+
+impl Separators {
+    /// `1,234.5`, as used by the formatters in this module
+    pub const ENGLISH: Self = Separators {
+        decimal: '.',
+        group: ',',
+    };
+    /// `1.234,5`, as used in much of continental Europe
+    pub const EUROPEAN: Self = Separators {
+        decimal: ',',
+        group: '.',
+    };
+    /// `1 234,5`, as used in France and recommended by the SI
+    pub const SPACED: Self = Separators {
+        decimal: ',',
+        group: ' ',
+    };
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// Formatters whose numbers can be punctuated with any `Separators`, as used by `Localized`
+pub trait Localize: Display {
+    /// Format as `Display` does without a width, but punctuating numbers with `separators`
+    fn fmt_with(&self, f: &mut fmt::Formatter, separators: Separators) -> fmt::Result;
+}
+
+/// Implement `Display` for `Localize` types, with English separators
+macro_rules! display_english {
+    ($($ty:ty),* $(,)*) => {
+        $(
+            impl Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    if f.width().is_some() {
+                        return padded(f, self);
+                    }
+                    self.fmt_with(f, Separators::ENGLISH)
+                }
+            }
+        )*
+    };
+}
+
+display_english!(
+    Scaled<'_>,
+    SigFigs,
+    PlainSigFigs,
+    Binary,
+    Scientific,
+    FixedBinary,
+    FixedScientific,
+    ByteSize,
+    ExactBinary,
+    ExactByteSize,
+    Rate<'_>,
+    Short,
+);
+
+/// Write the number formatted by `args`, which must contain no text other than digits, signs, exponents, and a
+/// decimal point, using the decimal separator of `separators`
+fn number(f: &mut fmt::Formatter, separators: Separators, args: fmt::Arguments) -> fmt::Result {
+    if separators.decimal == '.' {
+        return f.write_fmt(args);
+    }
+    for c in args.to_string().chars() {
+        f.write_char(if c == '.' { separators.decimal } else { c })?;
+    }
+    Ok(())
+}
+
+/// Adapter that punctuates the numbers written by any of the formatters in this module with other separators
+///
+/// Only the numbers are affected, so units and other text pass through unchanged.
+///
+/// # Examples
+/// ```
+/// # use yapb::prefix::{Binary, Grouped, Localized, Rate, Separators};
+/// assert_eq!(format!("{}B", Localized(Binary(12345.0), Separators::EUROPEAN)), "12,1 KiB");
+/// assert_eq!(format!("{}", Localized(Grouped(1234567, ','), Separators::EUROPEAN)), "1.234.567");
+/// assert_eq!(format!("{}", Localized(Rate(1500.0, "req."), Separators::SPACED)), "1,50 kreq./s");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Localized<T>(pub T, pub Separators);
+impl<T: Localize> Display for Localized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.width().is_some() {
+            return padded(f, self);
+        }
+        self.0.fmt_with(f, self.1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +759,17 @@ mod tests {
     fn si_prefixes() {
         assert_eq!(si(2e3), (2.0, "k"));
         assert_eq!(si(2e6), (2.0, "M"));
+        assert_eq!(si(1e-6), (1.0, "µ"));
+        assert_eq!(si(2e27).1, "R");
+        assert_eq!(si(2e30).1, "Q");
+        assert_eq!(si(2e-27).1, "r");
+        assert_eq!(si(2e-30).1, "q");
+        let small_only = Scale {
+            large: &[],
+            ..Scale::SI
+        };
+        assert_eq!(small_only.apply(5e3), (5e3, ""));
+        assert_eq!(small_only.apply(5e-3), (5.0, "m"));
     }
 
     #[test]
@@ -137,6 +782,22 @@ mod tests {
         assert_eq!(SigFigs(10.0, 2).to_string(), "10");
     }
 
+    #[test]
+    fn alignment() {
+        assert_eq!(format!("[{:>8}]", SigFigs(1.5, 3)), "[    1.50]");
+        assert_eq!(format!("[{:<8}]", Binary(2048.0)), "[2.00 Ki ]");
+        assert_eq!(format!("[{:*^8}]", Scientific(2e-5)), "[*20.0 µ*]");
+    }
+
+    #[test]
+    fn plain_sigfigs() {
+        assert_eq!(PlainSigFigs(0.0, 3).to_string(), "0.00");
+        assert_eq!(PlainSigFigs(1.0, 1).to_string(), "1");
+        assert_eq!(PlainSigFigs(0.1, 2).to_string(), "0.10");
+        assert_eq!(PlainSigFigs(-42.42, 3).to_string(), "-42.4");
+        assert_eq!(PlainSigFigs(99999.0, 2).to_string(), "100000");
+    }
+
     #[test]
     fn binary_fmt() {
         assert_eq!(Binary(0.0).to_string(), "0.00 ");
@@ -153,7 +814,122 @@ mod tests {
     fn scientific_fmt() {
         assert_eq!(Scientific(0.001).to_string(), "1.00 m");
         assert_eq!(Scientific(0.01).to_string(), "10.0 m");
+        assert_eq!(Scientific(2e-5).to_string(), "20.0 µ");
         assert_eq!(Scientific(2.0 * 1000.0).to_string(), "2.00 k");
         assert_eq!(Scientific(999.0 * 1000.0).to_string(), "999 k");
     }
+
+    #[test]
+    fn zero() {
+        assert_eq!(Scientific(0.0).to_string(), "0.00 ");
+        assert_eq!(Scientific(-0.0).to_string(), "0.00 ");
+        assert_eq!(Binary(0.0).to_string(), "0.00 ");
+        assert_eq!(Scale::SI.apply(0.0), (0.0, ""));
+    }
+
+    #[test]
+    fn byte_size() {
+        let b = |x, c| ByteSize(x, c).to_string();
+        assert_eq!(b(0.0, Convention::Si), "0.00 B");
+        assert_eq!(b(1e6, Convention::Si), "1.00 MB");
+        assert_eq!(b(1024.0 * 1024.0, Convention::Jedec), "1.00 MB");
+        assert_eq!(b(1024.0 * 1024.0, Convention::Binary), "1.00 MiB");
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        assert_eq!(
+            parse_binary(&Binary(3.0 * 1024.0).to_string(), ""),
+            Ok(3.0 * 1024.0)
+        );
+        assert_eq!(parse_si(&format!("{}B", Scientific(2e-5)), "B"), Ok(2e-5));
+        assert_eq!(parse_si("1.5EB", "B"), Ok(1.5e18));
+        assert_eq!(parse_si("1e3 B", "B"), Ok(1e3));
+        assert!(parse_si("kB", "B").is_err());
+        assert!(parse_si("1 XB", "B").is_err());
+        assert_eq!(parse_si("1 QB", "B"), Ok(1e30));
+    }
+
+    #[test]
+    fn short() {
+        let short = |x| Short(x).to_string();
+        assert_eq!(short(0.0), "0");
+        assert_eq!(short(999.4), "999");
+        assert_eq!(short(999.6), "1.0k");
+        assert_eq!(short(9_960.0), "10k");
+        assert_eq!(short(999_999.0), "1.0M");
+        assert_eq!(short(-1500.0), "-1.5k");
+        assert_eq!(short(2e15), "2000T");
+    }
+
+    #[test]
+    fn ratio() {
+        assert_eq!(Ratio(0, 7).to_string(), "0.00%");
+        assert_eq!(Ratio(7, 7).to_string(), "100%");
+        assert_eq!(Ratio(1, 100_000).to_string(), "0.00100%");
+        assert_eq!(format!("[{:>7.2}]", Ratio(1, 2)), "[    50%]");
+    }
+
+    #[test]
+    fn localized() {
+        let l = |x| Localized(x, Separators::SPACED).to_string();
+        assert_eq!(l(Scientific(1234.0)), "1,23 k");
+        assert_eq!(
+            Localized(Grouped(1234567, ','), Separators::SPACED).to_string(),
+            "1 234 567"
+        );
+        assert_eq!(
+            Localized(Grouped(1234567, ' '), Separators::EUROPEAN).to_string(),
+            "1.234.567"
+        );
+        assert_eq!(
+            Localized(Rate(2.5, "pkt,"), Separators::EUROPEAN).to_string(),
+            "2,50 pkt,/s"
+        );
+        assert_eq!(
+            format!("[{:>6.2}]", Localized(Ratio(1, 8), Separators::EUROPEAN)),
+            "[   13%]"
+        );
+        assert_eq!(
+            Localized(Short(1234.0), Separators::EUROPEAN).to_string(),
+            "1,2k"
+        );
+        assert_eq!(
+            Localized(FixedBinary(0.5), Separators::EUROPEAN).to_string(),
+            "  0,50 "
+        );
+    }
+
+    #[test]
+    fn exact() {
+        let x = 1000 * 1024u128.pow(5) - 1;
+        assert_eq!(ExactBinary(x).to_string(), "0.98 Ei");
+        assert_eq!(ExactBinary(999).to_string(), "999 ");
+        assert_eq!(ExactBinary(999 * 1024).to_string(), "999 Ki");
+        assert_eq!(ExactBinary(1023999).to_string(), "0.98 Mi");
+        assert_eq!(Binary(1023999.0).to_string(), "1000 Ki");
+        assert_eq!(ExactBinary(1000 * 1024).to_string(), "0.98 Mi");
+        assert_eq!(Binary(1000.0 * 1024.0).to_string(), "0.98 Mi");
+        assert_eq!(ExactBinary(u128::MAX).to_string(), "2.81e14 Yi");
+        assert_eq!(ExactByteSize(0, Convention::Si).to_string(), "0.00 B");
+        assert_eq!(
+            ExactByteSize(1 << 20, Convention::Jedec).to_string(),
+            "1.00 MB"
+        );
+    }
+
+    #[test]
+    fn fixed_width() {
+        for &x in &[
+            0.0, 0.01, 0.5, 1.0, 999.0, 1023.0, 12345.0, 1e9, 1e27, 1.2e27, 1.21e27, 5e27, 9.9e27,
+            1e32,
+        ] {
+            assert_eq!(FixedBinary(x).to_string().chars().count(), 7);
+        }
+        for &x in &[
+            1e-30, 1e-24, 2e-5, 0.01, 1.0, 999.0, 1e3, 12345.0, 1e26, 9e32,
+        ] {
+            assert_eq!(FixedScientific(x).to_string().chars().count(), 6);
+        }
+    }
 }
@@ -0,0 +1,110 @@
+//! Ready-made bar styles, selectable by name, so applications can offer a choice of looks without designing charsets
+
+use styles::Charset;
+use BarStyle;
+
+/// Description of a built-in bar style
+#[derive(Debug, Copy, Clone)]
+pub struct Preset {
+    /// Name accepted by `get`
+    pub name: &'static str,
+    pub style: BarStyle,
+    /// The least capable character repertoire that can render every glyph
+    pub charset: Charset,
+}
+
+/// Every built-in bar style
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "ascii",
+        style: BarStyle::ASCII,
+        charset: Charset::Ascii,
+    },
+    Preset {
+        name: "classic",
+        style: BarStyle {
+            full: "=",
+            partial: &[">"],
+            empty: None,
+        },
+        charset: Charset::Ascii,
+    },
+    Preset {
+        name: "shades",
+        style: BarStyle::CP437,
+        charset: Charset::Cp437,
+    },
+    Preset {
+        name: "heavy",
+        style: BarStyle {
+            full: "█",
+            partial: &["▌"],
+            empty: Some("░"),
+        },
+        charset: Charset::Cp437,
+    },
+    Preset {
+        name: "fine",
+        style: BarStyle::BLOCKS,
+        charset: Charset::Unicode,
+    },
+    Preset {
+        name: "fullwidth",
+        style: BarStyle::FULLWIDTH,
+        charset: Charset::Unicode,
+    },
+    Preset {
+        name: "dots",
+        style: BarStyle {
+            full: "⣿",
+            partial: &["⡀", "⡄", "⡆", "⡇", "⣇", "⣧", "⣷"],
+            empty: None,
+        },
+        charset: Charset::Braille,
+    },
+];
+
+/// Look up a built-in bar style by name
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::presets;
+/// let mut bar = Bar::with_style(presets::get("classic").unwrap());
+/// bar.set(0.6);
+/// assert_eq!(format!("[{:8}]", bar), "[====>   ]");
+/// assert!(presets::get("bogus").is_none());
+/// ```
+pub fn get(name: &str) -> Option<BarStyle> {
+    PRESETS.iter().find(|x| x.name == name).map(|x| x.style)
+}
+
+/// Styles that can be rendered by a device supporting `charset`
+pub fn compatible(charset: Charset) -> impl Iterator<Item = &'static Preset> {
+    PRESETS.iter().filter(move |x| x.charset <= charset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use width;
+    use {Bar, Progress};
+
+    #[test]
+    fn consistent() {
+        for (i, preset) in PRESETS.iter().enumerate() {
+            assert!(PRESETS[..i].iter().all(|x| x.name != preset.name));
+            let style = preset.style;
+            let cell = width::str_width(style.full);
+            for glyph in style.partial.iter().chain(style.empty.as_ref()) {
+                assert_eq!(width::str_width(glyph), cell, "{}", preset.name);
+            }
+            if preset.charset == Charset::Ascii {
+                assert!(style.full.is_ascii() && style.partial.iter().all(|x| x.is_ascii()));
+            }
+            let mut bar = Bar::with_style(style);
+            bar.set(0.3);
+            assert_eq!(width::str_width(&format!("{:10}", bar)), 10);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! `Widget` impls for drawing bars, sparklines, and composite widgets into `ratatui` layouts
+//!
+//! Widgets are drawn with the style already present in the buffer, so e.g. a `Block`'s style or a prior
+//! `Buffer::set_style` call determines their colors.
+
+use std::fmt::Display;
+
+use ratatui_core::buffer::Buffer;
+use ratatui_core::layout::Rect;
+use ratatui_core::style::Style;
+use ratatui_core::widgets::Widget;
+
+use html::sparkline_levels;
+use layout::{Row, StatusLine};
+use multi::MultiBar;
+use Bar;
+
+/// Draws the bar across the full width of every row of the area
+///
+/// # Examples
+/// ```
+/// # extern crate ratatui_core;
+/// # extern crate yapb;
+/// # use ratatui_core::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// # use yapb::*;
+/// # fn main() {
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+/// bar.render(buf.area, &mut buf);
+/// assert_eq!(buf, Buffer::with_lines(["██  "]));
+/// # }
+/// ```
+impl Widget for &Bar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let line = format!("{:1$}", self, usize::from(area.width));
+        for y in area.top()..area.bottom() {
+            buf.set_stringn(area.x, y, &line, usize::from(area.width), Style::new());
+        }
+    }
+}
+
+impl Widget for Bar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+/// Draws the line at the area's width on its first row
+impl<'a, 'b> Widget for &'a StatusLine<'b> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Lines(&self.display(usize::from(area.width))).render(area, buf);
+    }
+}
+
+/// Draws the row at the area's width on its first row
+impl<'a, 'b> Widget for &'a Row<'b> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Lines(&self.display(usize::from(area.width))).render(area, buf);
+    }
+}
+
+/// Draws one task per row, omitting any that don't fit
+impl Widget for &MultiBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (y, line) in (area.top()..area.bottom()).zip(self.lines(usize::from(area.width))) {
+            buf.set_stringn(area.x, y, &line, usize::from(area.width), Style::new());
+        }
+    }
+}
+
+/// Draws any `Display`, formatted at the area's width, with each line of output on a successive row
+///
+/// Lines beyond the area's height are omitted, and each is truncated to its width.
+///
+/// # Examples
+/// ```
+/// # extern crate ratatui_core;
+/// # extern crate yapb;
+/// # use ratatui_core::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// # use yapb::*;
+/// # use yapb::ratatui::Lines;
+/// # fn main() {
+/// let mut spinner = Line::new();
+/// spinner.step(1);
+/// let mut buf = Buffer::empty(Rect::new(0, 0, 3, 2));
+/// Lines(&spinner).render(buf.area, &mut buf);
+/// assert_eq!(buf, Buffer::with_lines(["/  ", "   "]));
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Lines<T>(pub T);
+
+impl<T: Display> Widget for Lines<T> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text = format!("{:1$}", self.0, usize::from(area.width));
+        for (y, line) in (area.top()..area.bottom()).zip(text.split('\n')) {
+            buf.set_stringn(area.x, y, line, usize::from(area.width), Style::new());
+        }
+    }
+}
+
+/// A sparkline of the most recent values that fit in the area's width, built from block elements and scaled between
+/// the minimum and maximum of those shown
+///
+/// Drawn on the area's first row, starting from its left edge.
+///
+/// # Examples
+/// ```
+/// # extern crate ratatui_core;
+/// # extern crate yapb;
+/// # use ratatui_core::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// # use yapb::ratatui::Sparkline;
+/// # fn main() {
+/// let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+/// Sparkline(&[9.0, 0.0, 1.0, 2.0, 7.0]).render(buf.area, &mut buf);
+/// assert_eq!(buf, Buffer::with_lines(["▁▂▃█"]));
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Sparkline<'a>(pub &'a [f32]);
+
+impl<'a> Widget for Sparkline<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let shown = &self.0[self.0.len().saturating_sub(usize::from(area.width))..];
+        let line = sparkline_levels(shown).collect::<String>();
+        buf.set_stringn(area.x, area.y, &line, usize::from(area.width), Style::new());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Progress;
+
+    #[test]
+    fn widgets() {
+        let mut bar = Bar::new();
+        bar.set(1.0);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 3));
+        bar.render(Rect::new(1, 1, 2, 2), &mut buf);
+        let line = StatusLine::new().text("[").flex(&bar).text("]");
+        Widget::render(&line, Rect::new(0, 0, 6, 1), &mut buf);
+        assert_eq!(buf, Buffer::with_lines(["[████]", " ██   ", " ██   "]));
+
+        let mut tasks = MultiBar::new();
+        tasks.add_bar("a");
+        tasks.add_bar("b");
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 1));
+        Widget::render(&tasks, buf.area, &mut buf);
+        assert_eq!(buf, Buffer::with_lines(["a [ ]"]));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 2));
+        Sparkline(&[3.0, 3.0]).render(Rect::new(1, 1, 3, 1), &mut buf);
+        Sparkline(&[]).render(buf.area, &mut buf);
+        Sparkline(&[1.0]).render(Rect::new(0, 0, 0, 1), &mut buf);
+        assert_eq!(buf, Buffer::with_lines(["    ", " ▁▁ "]));
+    }
+}
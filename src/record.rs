@@ -0,0 +1,305 @@
+//! Recording progress for later analysis or replay
+//!
+//! A `Recorder` keeps a timestamped log of progress that can be saved as text, inspected for stalls, or replayed
+//! through any widget to reproduce exactly what was shown, e.g. for demos or golden-output tests.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use Progress;
+
+/// A timestamped log of progress
+///
+/// The text form written by `Display` and read by `FromStr` has one entry per line: seconds since the first entry,
+/// progress, and the message, separated by spaces. Times are written to the nanosecond, so parsing the text restores
+/// the recording exactly.
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::record::Recorder;
+/// # use std::time::{Duration, Instant};
+/// let start = Instant::now();
+/// let mut recorder = Recorder::new();
+/// recorder.record(start, 0.0, "connecting");
+/// recorder.record(start + Duration::from_millis(1500), 0.5, "copying");
+/// recorder.record(start + Duration::from_secs(2), 1.0, "done");
+/// assert_eq!(
+///     recorder.to_string(),
+///     "0.000000000 0 connecting\n1.500000000 0.5 copying\n2.000000000 1 done\n"
+/// );
+///
+/// let mut frames = Vec::new();
+/// recorder.replay(&mut Bar::new(), Duration::from_secs(1), |at, bar, message| {
+///     frames.push(format!("{}s [{:4}] {}", at.as_secs(), bar, message));
+/// });
+/// assert_eq!(frames, ["0s [    ] connecting", "1s [    ] connecting", "2s [████] done"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorder {
+    start: Option<Instant>,
+    entries: Vec<Entry>,
+}
+
+/// The state of progress at one point in a recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Time since the first entry
+    pub at: Duration,
+    /// Progress, in [0, 1]
+    pub value: f32,
+    pub message: String,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that progress is `value` with `message` as of `now`
+    ///
+    /// Line breaks in `message` are replaced by spaces, so that each entry remains one line of text.
+    pub fn record(&mut self, now: Instant, value: f32, message: &str) {
+        let start = *self.start.get_or_insert(now);
+        self.entries.push(Entry {
+            at: now.saturating_duration_since(start),
+            value,
+            message: message.replace(['\r', '\n'], " "),
+        });
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Total time covered by the recording
+    pub fn duration(&self) -> Duration {
+        self.entries.last().map_or(Duration::from_secs(0), |x| x.at)
+    }
+
+    /// Periods of at least `threshold` over which progress didn't change, as their start and duration
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::record::Recorder;
+    /// # use std::time::Duration;
+    /// let recorder: Recorder = "0 0 a\n1 0.5 b\n2 0.5 c\n9 0.5 d\n10 1 e".parse().unwrap();
+    /// assert_eq!(
+    ///     recorder.stalls(Duration::from_secs(5)),
+    ///     [(Duration::from_secs(1), Duration::from_secs(9))]
+    /// );
+    /// ```
+    pub fn stalls(&self, threshold: Duration) -> Vec<(Duration, Duration)> {
+        let mut stalls = Vec::new();
+        let mut changed: Option<&Entry> = None;
+        for entry in &self.entries {
+            match changed {
+                Some(last) if last.value == entry.value => continue,
+                // Out-of-order timestamps count as no time passing
+                Some(last) if entry.at.saturating_sub(last.at) >= threshold => {
+                    stalls.push((last.at, entry.at - last.at))
+                }
+                _ => {}
+            }
+            changed = Some(entry);
+        }
+        if let Some(last) = changed {
+            let end = self.duration();
+            if end.saturating_sub(last.at) >= threshold {
+                stalls.push((last.at, end - last.at));
+            }
+        }
+        stalls
+    }
+
+    /// Play the recording back through `widget`, calling `frame` every `interval` with the time, the widget, and
+    /// the current message
+    ///
+    /// Frames run from the start of the recording through its latest entry. Entries are applied in the order they were
+    /// recorded, so one timestamped earlier than its predecessor takes effect alongside it. A zero `interval` produces
+    /// one frame per entry instead.
+    pub fn replay<P: Progress>(
+        &self,
+        widget: &mut P,
+        interval: Duration,
+        mut frame: impl FnMut(Duration, &P, &str),
+    ) {
+        if interval == Duration::from_secs(0) {
+            for entry in &self.entries {
+                widget.set(entry.value);
+                frame(entry.at, widget, &entry.message);
+            }
+            return;
+        }
+        // Not `duration`, which is the last entry's time and may precede an earlier, out-of-order entry
+        let end = self.entries.iter().map(|x| x.at).max().unwrap_or_default();
+        let mut next = 0;
+        let mut message = "";
+        let mut at = Duration::from_secs(0);
+        while next < self.entries.len() {
+            while let Some(entry) = self.entries.get(next).filter(|x| x.at <= at) {
+                widget.set(entry.value);
+                message = &entry.message;
+                next += 1;
+            }
+            frame(at, widget, message);
+            at += interval;
+            if next < self.entries.len() && at > end {
+                // Finish on the final entry rather than skipping past it
+                at = end;
+            }
+        }
+    }
+}
+
+impl Display for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in &self.entries {
+            // Formatted from the integer parts, since an `f64` of seconds can't hold nanoseconds for long recordings
+            writeln!(
+                f,
+                "{}.{:09} {} {}",
+                entry.at.as_secs(),
+                entry.at.subsec_nanos(),
+                entry.value,
+                entry.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recorder {
+    type Err = ParseRecordingError;
+    fn from_str(s: &str) -> Result<Self, ParseRecordingError> {
+        let mut entries = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let error = ParseRecordingError { line: i + 1 };
+            let mut fields = line.splitn(3, ' ');
+            let at = fields.next().and_then(parse_seconds).ok_or(error)?;
+            let value = fields.next().and_then(|x| x.parse().ok()).ok_or(error)?;
+            entries.push(Entry {
+                at,
+                value,
+                message: fields.next().unwrap_or("").into(),
+            });
+        }
+        Ok(Recorder {
+            start: None,
+            entries,
+        })
+    }
+}
+
+/// Parse a non-negative number of seconds, exactly to the nanosecond when written in plain decimal
+fn parse_seconds(s: &str) -> Option<Duration> {
+    parse_decimal_seconds(s)
+        // Anything else `f64` accepts, e.g. an exponent, is still understood, albeit approximately
+        .or_else(|| Duration::try_from_secs_f64(s.parse().ok()?).ok())
+}
+
+/// Parse digits with an optional fractional part of at most 9 digits
+fn parse_decimal_seconds(s: &str) -> Option<Duration> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let digits = |x: &str| x.bytes().all(|x| x.is_ascii_digit());
+    if whole.len() + fraction.len() == 0
+        || fraction.len() > 9
+        || !digits(whole)
+        || !digits(fraction)
+    {
+        return None;
+    }
+    let secs = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let nanos = fraction
+        .bytes()
+        .chain((fraction.len()..9).map(|_| b'0'))
+        .fold(0, |acc, x| acc * 10 + u32::from(x - b'0'));
+    Some(Duration::new(secs, nanos))
+}
+
+/// Error returned when parsing a `Recorder` from malformed text
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct ParseRecordingError {
+    /// Line number of the malformed entry, counting from 1
+    pub line: usize,
+}
+
+impl Display for ParseRecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "malformed recording entry on line {}", self.line)
+    }
+}
+
+impl Error for ParseRecordingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Bar;
+
+    #[test]
+    fn roundtrip() {
+        let start = Instant::now();
+        let mut recorder = Recorder::new();
+        recorder.record(start, 0.25, "a b\nc");
+        recorder.record(start + Duration::from_millis(10), 0.75, "");
+        recorder.record(start + Duration::new(31_536_000, 123_457), 1.0, "");
+        let parsed = recorder.to_string().parse::<Recorder>().unwrap();
+        assert_eq!(parsed.entries(), recorder.entries());
+        assert_eq!(parsed.entries()[0].message, "a b c");
+        assert_eq!(
+            "1e3 0 a\n.5 0 b\n 0 c".parse::<Recorder>(),
+            Err(ParseRecordingError { line: 3 })
+        );
+        assert_eq!(
+            "0 0 a\nx 1".parse::<Recorder>(),
+            Err(ParseRecordingError { line: 2 })
+        );
+    }
+
+    #[test]
+    fn replay() {
+        let recorder: Recorder = "0 0 a\n0 0.5 b\n2.5 1 c".parse().unwrap();
+        let mut frames = Vec::new();
+        recorder.replay(
+            &mut Bar::new(),
+            Duration::from_secs(1),
+            |at, bar, message| {
+                frames.push(format!("{} {:2} {}", at.as_millis(), bar, message));
+            },
+        );
+        assert_eq!(frames, ["0 █  b", "1000 █  b", "2000 █  b", "2500 ██ c"]);
+        frames.clear();
+        recorder.replay(&mut Bar::new(), Duration::from_secs(0), |at, bar, _| {
+            frames.push(format!("{} {:2}", at.as_millis(), bar));
+        });
+        assert_eq!(frames, ["0   ", "0 █ ", "2500 ██"]);
+        assert_eq!(Recorder::new().stalls(Duration::from_secs(0)), []);
+    }
+
+    #[test]
+    fn stalls_out_of_order() {
+        let recorder: Recorder = "5 0 a\n1 0.5 b".parse().unwrap();
+        assert_eq!(recorder.stalls(Duration::from_secs(1)), []);
+        let mut frames = Vec::new();
+        recorder.replay(
+            &mut Bar::new(),
+            Duration::from_secs(2),
+            |at, bar, message| {
+                frames.push(format!("{} {:2} {}", at.as_secs(), bar, message));
+            },
+        );
+        assert_eq!(frames, ["0    ", "2    ", "4    ", "5 █  b"]);
+        let recorder: Recorder = "0 0 a\n5 0.5 b\n1 0.5 c".parse().unwrap();
+        assert_eq!(
+            recorder.stalls(Duration::from_secs(1)),
+            [(Duration::from_secs(0), Duration::from_secs(5))]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Backend for `crossterm`, which supports Windows consoles as well as ANSI terminals
+
+use std::io::{self, Write};
+
+use crossterm::cursor::{Hide, MoveToColumn, MoveToPreviousLine, Show};
+use crossterm::queue;
+use crossterm::terminal::{self, Clear, ClearType};
+
+use super::Backend;
+
+/// Drives any writer, typically `io::stdout()`, with `crossterm`
+///
+/// # Examples
+/// ```no_run
+/// # use std::{io, thread, time::Duration};
+/// # use yapb::*;
+/// # use yapb::render::{crossterm::Crossterm, Driver};
+/// let mut driver = Driver::new(Crossterm(io::stdout()));
+/// let mut bar = Bar::new().framed("[", "]");
+/// for i in 0..=100 {
+///     bar.set(i as f32 / 100.0);
+///     let width = driver.width();
+///     driver.draw(format_args!("{:1$}", bar, width)).unwrap();
+///     thread::sleep(Duration::from_millis(50));
+/// }
+/// driver.finish().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Crossterm<W>(pub W);
+
+impl<W: Write> Write for Crossterm<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: Write> Backend for Crossterm<W> {
+    fn width(&self) -> io::Result<u16> {
+        terminal::size().map(|(columns, _)| columns)
+    }
+
+    fn move_up(&mut self, lines: u16) -> io::Result<()> {
+        match lines {
+            0 => queue!(self.0, MoveToColumn(0)),
+            n => queue!(self.0, MoveToPreviousLine(n)),
+        }
+    }
+
+    fn clear_below(&mut self) -> io::Result<()> {
+        queue!(self.0, Clear(ClearType::FromCursorDown))
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        queue!(self.0, Hide)
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        queue!(self.0, Show)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences() {
+        let mut backend = Crossterm(Vec::new());
+        backend.move_up(0).unwrap();
+        backend.move_up(2).unwrap();
+        backend.clear_below().unwrap();
+        assert_eq!(backend.0, b"\x1b[1G\x1b[2F\x1b[J");
+    }
+}
@@ -0,0 +1,193 @@
+//! Drivers that write the crate's `Display` output to a terminal
+//!
+//! The widgets themselves remain IO-free; this optional subsystem only takes care of redrawing a frame in place. A
+//! `Driver` does the bookkeeping, and a `Backend` provides the few terminal operations it needs. Backends for
+//! `crossterm` and `termion` are available under features of the same names.
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use width;
+
+#[cfg(feature = "crossterm")]
+pub mod crossterm;
+#[cfg(feature = "termion")]
+pub mod termion;
+
+/// Terminal operations needed to redraw a region in place
+pub trait Backend: Write {
+    /// Width of the terminal in cells
+    fn width(&self) -> io::Result<u16>;
+    /// Move the cursor to the first column, `lines` lines above its current line
+    fn move_up(&mut self, lines: u16) -> io::Result<()>;
+    /// Clear everything from the cursor to the end of the screen
+    fn clear_below(&mut self) -> io::Result<()>;
+    fn hide_cursor(&mut self) -> io::Result<()>;
+    fn show_cursor(&mut self) -> io::Result<()>;
+}
+
+/// Draws successive frames over one another at the cursor position
+///
+/// Each frame may span several lines. Lines are truncated to the terminal's width, and the lines of the previous frame
+/// that wrap after the terminal is narrowed are accounted for, so resizing can't throw off the position of the next
+/// frame. The cursor is hidden while frames are being drawn, and shown again by `finish` or when the driver is dropped.
+#[derive(Debug)]
+pub struct Driver<B: Backend> {
+    backend: B,
+    /// Widths of the lines of the previous frame, if it's still displayed
+    drawn: Option<Vec<usize>>,
+    hidden: bool,
+}
+
+impl<B: Backend> Driver<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            drawn: None,
+            hidden: false,
+        }
+    }
+
+    /// Width of the terminal in cells, or 80 if it can't be determined
+    pub fn width(&self) -> usize {
+        self.backend.width().map_or(80, usize::from)
+    }
+
+    /// Replace the previous frame with `frame`
+    pub fn draw(&mut self, frame: impl Display) -> io::Result<()> {
+        let width = self.width();
+        self.rewind(width)?;
+        if !self.hidden {
+            self.backend.hide_cursor()?;
+            self.hidden = true;
+        }
+        let frame = frame.to_string();
+        let mut drawn = Vec::new();
+        for (i, line) in frame.split('\n').enumerate() {
+            if i != 0 {
+                self.backend.write_all(b"\r\n")?;
+            }
+            let line = width::truncate(line, width);
+            self.backend.write_all(line.as_bytes())?;
+            drawn.push(width::str_width(line));
+        }
+        self.drawn = Some(drawn);
+        self.backend.flush()
+    }
+
+    /// Erase the previous frame, leaving the cursor where it began
+    pub fn clear(&mut self) -> io::Result<()> {
+        let width = self.width();
+        self.rewind(width)?;
+        self.drawn = None;
+        self.backend.flush()
+    }
+
+    /// Leave the previous frame in place, show the cursor, and move to the next line so that any further frames are
+    /// drawn beneath it
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.drawn.take().is_some() {
+            self.backend.write_all(b"\r\n")?;
+        }
+        if self.hidden {
+            self.backend.show_cursor()?;
+            self.hidden = false;
+        }
+        self.backend.flush()
+    }
+
+    pub fn get_ref(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// Move to the start of the previous frame, as displayed at `width`, and clear everything after it
+    fn rewind(&mut self, width: usize) -> io::Result<()> {
+        let drawn = match self.drawn {
+            None => return Ok(()),
+            Some(ref x) => x,
+        };
+        // Rows occupied by each line, which may now wrap if the terminal has been narrowed
+        let rows = drawn
+            .iter()
+            .map(|&x| x.div_ceil(width.max(1)).max(1))
+            .sum::<usize>();
+        self.backend
+            .move_up((rows - 1).min(usize::from(u16::MAX)) as u16)?;
+        self.backend.clear_below()
+    }
+}
+
+impl<B: Backend> Drop for Driver<B> {
+    fn drop(&mut self) {
+        if self.hidden {
+            let _ = self.backend.show_cursor();
+            let _ = self.backend.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records operations as text
+    struct Log {
+        out: String,
+        width: u16,
+    }
+
+    impl Write for Log {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.out.push_str(std::str::from_utf8(buf).unwrap());
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for Log {
+        fn width(&self) -> io::Result<u16> {
+            Ok(self.width)
+        }
+        fn move_up(&mut self, lines: u16) -> io::Result<()> {
+            self.out.push_str(&format!("<up {}>", lines));
+            Ok(())
+        }
+        fn clear_below(&mut self) -> io::Result<()> {
+            self.out.push_str("<clear>");
+            Ok(())
+        }
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.out.push_str("<hide>");
+            Ok(())
+        }
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.out.push_str("<show>");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn redraw() {
+        let mut driver = Driver::new(Log {
+            out: String::new(),
+            width: 4,
+        });
+        driver.draw("abcdef\nb").unwrap();
+        assert_eq!(driver.get_ref().out, "<hide>abcd\r\nb");
+        driver.get_mut().out.clear();
+        driver.get_mut().width = 2;
+        driver.draw("c").unwrap();
+        driver.finish().unwrap();
+        assert_eq!(driver.get_ref().out, "<up 2><clear>c\r\n<show>");
+        driver.get_mut().out.clear();
+        driver.clear().unwrap();
+        driver.draw("d").unwrap();
+        assert_eq!(driver.get_ref().out, "<hide>d");
+    }
+}
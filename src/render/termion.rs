@@ -0,0 +1,61 @@
+//! Backend for `termion`
+
+use std::io::{self, Write};
+
+use termion::{clear, cursor, terminal_size};
+
+use super::Backend;
+
+/// Drives any writer, typically `io::stdout()`, with `termion`
+#[derive(Debug)]
+pub struct Termion<W>(pub W);
+
+impl<W: Write> Write for Termion<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: Write> Backend for Termion<W> {
+    fn width(&self) -> io::Result<u16> {
+        terminal_size().map(|(columns, _)| columns)
+    }
+
+    fn move_up(&mut self, lines: u16) -> io::Result<()> {
+        self.0.write_all(b"\r")?;
+        if lines != 0 {
+            write!(self.0, "{}", cursor::Up(lines))?;
+        }
+        Ok(())
+    }
+
+    fn clear_below(&mut self) -> io::Result<()> {
+        write!(self.0, "{}", clear::AfterCursor)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        write!(self.0, "{}", cursor::Hide)
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        write!(self.0, "{}", cursor::Show)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences() {
+        let mut backend = Termion(Vec::new());
+        backend.move_up(0).unwrap();
+        backend.move_up(2).unwrap();
+        backend.clear_below().unwrap();
+        assert_eq!(backend.0, b"\r\r\x1b[2A\x1b[J");
+    }
+}
@@ -0,0 +1,235 @@
+//! Progress reported from other threads over a channel
+//!
+//! Producers hold cloneable `Reporter`s and send events as work proceeds. The rendering thread calls
+//! `Receiver::update` before each redraw to fold any pending events into per-task `Status`es, from which widgets are
+//! then updated.
+
+use std::sync::mpsc;
+
+/// Create a connected `Reporter` and `Receiver`
+///
+/// # Examples
+/// ```
+/// # use std::thread;
+/// # use yapb::*;
+/// # use yapb::report;
+/// let (reporter, mut receiver) = report::channel();
+/// thread::spawn(move || {
+///     reporter.length("download", 4);
+///     reporter.inc("download", 1);
+///     reporter.message("download", "connecting");
+/// })
+/// .join()
+/// .unwrap();
+/// receiver.update();
+/// let status = receiver.get(&"download").unwrap();
+/// let mut bar = Bar::new();
+/// bar.set(status.fraction());
+/// assert_eq!(format!("{:4} {}", bar, status.message()), "█    connecting");
+/// ```
+pub fn channel<K>() -> (Reporter<K>, Receiver<K>) {
+    let (send, recv) = mpsc::channel();
+    (
+        Reporter { send },
+        Receiver {
+            recv,
+            tasks: Vec::new(),
+            connected: true,
+        },
+    )
+}
+
+/// A change in the progress of a task
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Set the total amount of work
+    Length(u64),
+    /// Set the amount of work done
+    Set(u64),
+    /// Add to the amount of work done
+    Inc(u64),
+    /// Replace the task's message
+    Message(String),
+    /// Mark the task complete
+    Finish,
+}
+
+/// Sending half of a progress channel, which may be cloned for each producer
+///
+/// Events sent after the `Receiver` is dropped are silently discarded, so producers needn't care whether anything is
+/// still displaying their progress.
+#[derive(Debug)]
+pub struct Reporter<K> {
+    send: mpsc::Sender<(K, Event)>,
+}
+
+impl<K> Clone for Reporter<K> {
+    fn clone(&self) -> Self {
+        Self {
+            send: self.send.clone(),
+        }
+    }
+}
+
+impl<K> Reporter<K> {
+    /// Send an arbitrary event concerning task `key`
+    pub fn send(&self, key: K, event: Event) {
+        let _ = self.send.send((key, event));
+    }
+
+    pub fn length(&self, key: K, length: u64) {
+        self.send(key, Event::Length(length));
+    }
+
+    pub fn set(&self, key: K, position: u64) {
+        self.send(key, Event::Set(position));
+    }
+
+    pub fn inc(&self, key: K, delta: u64) {
+        self.send(key, Event::Inc(delta));
+    }
+
+    pub fn message(&self, key: K, message: &str) {
+        self.send(key, Event::Message(message.into()));
+    }
+
+    pub fn finish(&self, key: K) {
+        self.send(key, Event::Finish);
+    }
+}
+
+/// Receiving half of a progress channel, accumulating the state of each task
+#[derive(Debug)]
+pub struct Receiver<K> {
+    recv: mpsc::Receiver<(K, Event)>,
+    tasks: Vec<(K, Status)>,
+    connected: bool,
+}
+
+impl<K: PartialEq> Receiver<K> {
+    /// Apply all pending events without blocking, returning whether there were any
+    ///
+    /// Tasks are created on their first event and kept in that order.
+    pub fn update(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            let (key, event) = match self.recv.try_recv() {
+                Ok(x) => x,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.connected = false;
+                    break;
+                }
+            };
+            changed = true;
+            let status = match self.tasks.iter().position(|x| x.0 == key) {
+                Some(i) => &mut self.tasks[i].1,
+                None => {
+                    self.tasks.push((key, Status::default()));
+                    &mut self.tasks.last_mut().unwrap().1
+                }
+            };
+            status.apply(event);
+        }
+        changed
+    }
+
+    pub fn get(&self, key: &K) -> Option<&Status> {
+        self.tasks.iter().find(|x| x.0 == *key).map(|x| &x.1)
+    }
+
+    /// Stop tracking a task, returning its final status
+    pub fn remove(&mut self, key: &K) -> Option<Status> {
+        let i = self.tasks.iter().position(|x| x.0 == *key)?;
+        Some(self.tasks.remove(i).1)
+    }
+
+    /// All known tasks, in order of their first event
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &Status)> {
+        self.tasks.iter().map(|x| (&x.0, &x.1))
+    }
+
+    /// Whether every `Reporter` has been dropped, as of the last `update`
+    ///
+    /// No further events can arrive, so the caller can render a final frame and stop.
+    pub fn is_disconnected(&self) -> bool {
+        !self.connected
+    }
+}
+
+/// Accumulated state of a task
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Status {
+    length: Option<u64>,
+    position: u64,
+    message: String,
+    finished: bool,
+}
+
+impl Status {
+    fn apply(&mut self, event: Event) {
+        match event {
+            Event::Length(x) => self.length = Some(x),
+            Event::Set(x) => self.position = x,
+            Event::Inc(x) => self.position = self.position.saturating_add(x),
+            Event::Message(x) => self.message = x,
+            Event::Finish => self.finished = true,
+        }
+    }
+
+    /// Total amount of work, if it has been reported
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// Amount of work done
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Fraction of work done, suitable for `Progress::set`
+    ///
+    /// Finished tasks are complete regardless of position, and tasks of unknown or zero length have made no progress.
+    pub fn fraction(&self) -> f32 {
+        if self.finished {
+            return 1.0;
+        }
+        match self.length {
+            Some(length) if length != 0 => (self.position as f64 / length as f64).min(1.0) as f32,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold() {
+        let (reporter, mut receiver) = channel();
+        assert!(!receiver.update());
+        reporter.clone().set(2, 5);
+        reporter.length(1, 10);
+        reporter.inc(1, 4);
+        reporter.send(1, Event::Inc(1));
+        assert!(receiver.update());
+        assert_eq!(receiver.iter().map(|x| *x.0).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(receiver.get(&1).unwrap().fraction(), 0.5);
+        assert_eq!(receiver.get(&2).unwrap().fraction(), 0.0);
+        reporter.finish(2);
+        drop(reporter);
+        assert!(receiver.update());
+        assert!(receiver.is_disconnected());
+        assert!(receiver.remove(&2).unwrap().is_finished());
+        assert!(receiver.get(&2).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+//! Pixel-precise bars and sparklines as sixel graphics (experimental)
+//!
+//! Sixels are supported by e.g. xterm (with `-ti vt340`), foot, WezTerm, and mlterm. Other terminals print garbage or
+//! nothing at all, so output should only be sent after confirming support, e.g. by checking a response to the primary
+//! device attributes query `ESC [ c` with `supports_sixel`, and text shown otherwise. `Fallback` makes that choice.
+
+use std::fmt::{self, Display, Write};
+
+use theme::Theme;
+
+/// Dimensions in pixels and colors of rendered images
+///
+/// # Examples
+/// ```
+/// # use yapb::sixel::Sixel;
+/// let image = Sixel { width: 4, height: 6, ..Sixel::default() };
+/// assert_eq!(
+///     image.bar(0.5).to_string(),
+///     "\x1bP0;1;0q\"1;1;4;6#0;2;88;88;88#1;2;30;69;31#0??~~$#1~~??-\x1b\\"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sixel {
+    pub width: u16,
+    pub height: u16,
+    /// RGB color of completed progress, or of the area under a sparkline
+    pub fill: (u8, u8, u8),
+    /// RGB color of remaining progress
+    pub background: (u8, u8, u8),
+}
+
+impl Default for Sixel {
+    fn default() -> Self {
+        Self {
+            width: 200,
+            height: 12,
+            fill: (0x4c, 0xaf, 0x50),
+            background: (0xe0, 0xe0, 0xe0),
+        }
+    }
+}
+
+impl Sixel {
+    /// Default dimensions, with colors taken from `theme`'s `filled` and `empty` unless they're `Color::Default`
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::sixel::Sixel;
+    /// # use yapb::theme::Theme;
+    /// assert_eq!(Sixel::with_theme(&Theme::CLASSIC).fill, (0x00, 0xcd, 0x00));
+    /// ```
+    pub fn with_theme(theme: &Theme) -> Self {
+        let default = Self::default();
+        Self {
+            fill: theme.filled.rgb().unwrap_or(default.fill),
+            background: theme.empty.rgb().unwrap_or(default.background),
+            ..default
+        }
+    }
+
+    /// A horizontal bar filled to `fraction`
+    pub fn bar(&self, fraction: f32) -> impl Display {
+        Image {
+            config: *self,
+            kind: Kind::Bar(fraction.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// An area chart of `values`, scaled to fill the image between zero and their maximum
+    pub fn sparkline<'a>(&self, values: &'a [f32]) -> impl Display + 'a {
+        Image {
+            config: *self,
+            kind: Kind::Sparkline(values),
+        }
+    }
+}
+
+struct Image<'a> {
+    config: Sixel,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Bar(f32),
+    Sparkline(&'a [f32]),
+}
+
+impl<'a> Image<'a> {
+    /// Whether the pixel at (`x`, `y`), measured from the top left, is filled
+    fn filled(&self, x: u16, y: u16) -> bool {
+        let Sixel { width, height, .. } = self.config;
+        match self.kind {
+            Kind::Bar(fraction) => f32::from(x) < f32::from(width) * fraction,
+            Kind::Sparkline(values) => {
+                if values.is_empty() {
+                    return false;
+                }
+                let max = values.iter().cloned().fold(0.0, f32::max);
+                let value = values[usize::from(x) * values.len() / usize::from(width)];
+                let level = if max > 0.0 { value.max(0.0) / max } else { 0.0 };
+                f32::from(height - y) <= (f32::from(height) * level).round()
+            }
+        }
+    }
+}
+
+impl<'a> Display for Image<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Sixel {
+            width,
+            height,
+            fill,
+            background,
+        } = self.config;
+        // Transparent background, raster attributes, and color registers as RGB percentages
+        write!(f, "\x1bP0;1;0q\"1;1;{};{}", width, height)?;
+        for (i, &(r, g, b)) in [background, fill].iter().enumerate() {
+            let percent = |x: u8| (u32::from(x) * 100 + 127) / 255;
+            write!(f, "#{};2;{};{};{}", i, percent(r), percent(g), percent(b))?;
+        }
+        // Each band covers six rows, drawn once per color
+        for band in (0..height).step_by(6) {
+            for (color, filled) in [false, true].iter().enumerate() {
+                if color != 0 {
+                    f.write_char('$')?;
+                }
+                write!(f, "#{}", color)?;
+                let mut run = (0, '?');
+                for x in 0..width {
+                    let mut bits = 0;
+                    for row in 0..6.min(height - band) {
+                        if self.filled(x, band + row) == *filled {
+                            bits |= 1 << row;
+                        }
+                    }
+                    let c = char::from(63 + bits);
+                    if c == run.1 {
+                        run.0 += 1;
+                    } else {
+                        write_run(f, run)?;
+                        run = (1, c);
+                    }
+                }
+                write_run(f, run)?;
+            }
+            f.write_char('-')?;
+        }
+        f.write_str("\x1b\\")
+    }
+}
+
+/// Write `count` repetitions of a sixel, compressed if that's shorter
+fn write_run(f: &mut fmt::Formatter, (count, c): (u16, char)) -> fmt::Result {
+    match count {
+        0 => Ok(()),
+        1..=3 => (0..count).try_for_each(|_| f.write_char(c)),
+        _ => write!(f, "!{}{}", count, c),
+    }
+}
+
+/// Whether a terminal's response to the primary device attributes query `ESC [ c` advertises sixel support
+///
+/// # Examples
+/// ```
+/// # use yapb::sixel::supports_sixel;
+/// assert!(supports_sixel("\x1b[?62;4;22c"));
+/// assert!(!supports_sixel("\x1b[?62;22c"));
+/// ```
+pub fn supports_sixel(response: &str) -> bool {
+    response
+        .trim_start_matches("\x1b[?")
+        .trim_end_matches('c')
+        .split(';')
+        .skip(1)
+        .any(|x| x == "4")
+}
+
+/// Displays `graphic` if the terminal supports it, and `text` otherwise
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::sixel::{Fallback, Sixel};
+/// let mut bar = Bar::new();
+/// bar.set(0.5);
+/// let image = Sixel::default().bar(bar.get());
+/// let text = format!("{:4}", bar);
+/// assert_eq!(Fallback { graphic: image, text, supported: false }.to_string(), "██  ");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Fallback<G, T> {
+    pub graphic: G,
+    pub text: T,
+    pub supported: bool,
+}
+
+impl<G: Display, T: Display> Display for Fallback<G, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.supported {
+            self.graphic.fmt(f)
+        } else {
+            self.text.fmt(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline() {
+        let image = Sixel {
+            width: 8,
+            height: 7,
+            ..Sixel::default()
+        };
+        assert_eq!(
+            image.sparkline(&[1.0, 2.0, 0.0, -1.0]).to_string(),
+            "\x1bP0;1;0q\"1;1;8;7#0;2;88;88;88#1;2;30;69;31\
+             #0FF??!4~$#1ww~~!4?-#0!4?!4@$#1!4@!4?-\x1b\\"
+        );
+        assert_eq!(
+            image.sparkline(&[]).to_string(),
+            "\x1bP0;1;0q\"1;1;8;7#0;2;88;88;88#1;2;30;69;31#0!8~$#1!8?-#0!8@$#1!8?-\x1b\\"
+        );
+        assert!(!supports_sixel(""));
+    }
+}
@@ -0,0 +1,228 @@
+//! Machine-readable progress state
+//!
+//! A `Snapshot` captures the same state that drives a human-readable status line, so a program can emit structured
+//! progress records for log aggregation alongside, or instead of, an animated display. Build one with
+//! `Snapshot::capture` from the trackers that drive the display, and render the human-readable form with its `Display`
+//! impl, so that the two outputs can't disagree.
+
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+use duration::Human;
+use estimate::{Eta, RateEstimator};
+use prefix::{PlainSigFigs, Scientific};
+use Throughput;
+
+/// Progress state at an instant, serializable as JSON
+///
+/// Durations are represented as fractional seconds, and unknown values as `null`.
+///
+/// # Examples
+/// ```
+/// # use std::time::Duration;
+/// # use yapb::snapshot::Snapshot;
+/// let snapshot = Snapshot {
+///     fraction: Some(0.5),
+///     rate: Some(1024.0),
+///     eta: Some(Duration::from_millis(2500)),
+///     elapsed: Duration::from_secs(3),
+///     message: "copying \"a.txt\"",
+/// };
+/// assert_eq!(
+///     snapshot.to_json(),
+///     r#"{"fraction":0.5,"rate":1024.0,"eta":2.5,"elapsed":3.0,"message":"copying \"a.txt\""}"#
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Snapshot<'a> {
+    /// Fraction of work completed, if the total is known
+    pub fraction: Option<f32>,
+    /// Units of work completed per second, if known
+    pub rate: Option<f64>,
+    /// Estimated time remaining, if known
+    pub eta: Option<Duration>,
+    /// Time since work began
+    pub elapsed: Duration,
+    pub message: &'a str,
+}
+
+impl<'a> Snapshot<'a> {
+    /// Capture the current state of `throughput` and `eta`
+    ///
+    /// The fraction is the progress most recently passed to `Eta::update`. The rate is unknown until `throughput` has
+    /// seen a full interval.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use yapb::Throughput;
+    /// # use yapb::estimate::{Eta, Kalman};
+    /// # use yapb::snapshot::Snapshot;
+    /// let start = Instant::now();
+    /// let mut throughput = Throughput::new(0.5, Duration::from_secs(1));
+    /// let mut eta = Eta::new(Kalman::new(1e-4, 1e-2));
+    /// throughput.record(start, 0);
+    /// eta.update(start, 0.0);
+    /// throughput.record(start + Duration::from_secs(1), 2048);
+    /// eta.update(start + Duration::from_secs(1), 0.25);
+    /// let snapshot = Snapshot::capture(&throughput, &eta, Duration::from_secs(1), "copying");
+    /// assert_eq!(snapshot.fraction, Some(0.25));
+    /// assert_eq!(snapshot.rate, Some(2048.0));
+    /// assert_eq!(snapshot.eta, Some(Duration::from_secs(3)));
+    /// assert_eq!(snapshot.to_string(), "copying: 25.0%, 2.05 k/s, 3s left, 1s elapsed");
+    /// ```
+    pub fn capture<E: RateEstimator>(
+        throughput: &Throughput,
+        eta: &Eta<E>,
+        elapsed: Duration,
+        message: &'a str,
+    ) -> Self {
+        Self {
+            fraction: eta.fraction().map(|x| x as f32),
+            rate: throughput.rate,
+            eta: eta.remaining(),
+            elapsed,
+            message,
+        }
+    }
+
+    /// Serialize as a single-line JSON object
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out)
+            .expect("writing to a String can't fail");
+        out
+    }
+
+    fn write_json(&self, out: &mut String) -> fmt::Result {
+        out.push_str("{\"fraction\":");
+        number(out, self.fraction.filter(|x| x.is_finite()))?;
+        out.push_str(",\"rate\":");
+        number(out, self.rate.filter(|x| x.is_finite()))?;
+        out.push_str(",\"eta\":");
+        number(out, self.eta.map(|x| x.as_secs_f64()))?;
+        out.push_str(",\"elapsed\":");
+        number(out, Some(self.elapsed.as_secs_f64()))?;
+        out.push_str(",\"message\":\"");
+        for c in self.message.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+                c => out.push(c),
+            }
+        }
+        out.push_str("\"}");
+        Ok(())
+    }
+}
+
+/// Displays the human-readable status line, e.g. `copying: 25.0%, 2.05 k/s, 3s left, 1s elapsed`
+///
+/// Unknown values are omitted, as is the message if empty.
+impl<'a> fmt::Display for Snapshot<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.message.is_empty() {
+            write!(f, "{}: ", self.message)?;
+        }
+        if let Some(fraction) = self.fraction.filter(|x| x.is_finite()) {
+            write!(f, "{}%, ", PlainSigFigs(f64::from(fraction) * 100.0, 3))?;
+        }
+        if let Some(rate) = self.rate.filter(|x| x.is_finite()) {
+            write!(f, "{}/s, ", Scientific(rate))?;
+        }
+        if let Some(eta) = self.eta {
+            write!(f, "{} left, ", Human(eta))?;
+        }
+        write!(f, "{} elapsed", Human(self.elapsed))
+    }
+}
+
+/// Write a finite JSON number, or `null`
+///
+/// `Debug` formatting always includes a decimal point or exponent, matching `serde_json`.
+fn number<T: fmt::Debug>(out: &mut String, x: Option<T>) -> fmt::Result {
+    match x {
+        Some(x) => write!(out, "{:?}", x),
+        None => {
+            out.push_str("null");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> ::serde::Serialize for Snapshot<'a> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("Snapshot", 5)?;
+        s.serialize_field("fraction", &self.fraction.filter(|x| x.is_finite()))?;
+        s.serialize_field("rate", &self.rate.filter(|x| x.is_finite()))?;
+        s.serialize_field("eta", &self.eta.map(|x| x.as_secs_f64()))?;
+        s.serialize_field("elapsed", &self.elapsed.as_secs_f64())?;
+        s.serialize_field("message", self.message)?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        let snapshot = Snapshot {
+            rate: Some(f64::NAN),
+            message: "\u{1}\n",
+            ..Snapshot::default()
+        };
+        assert_eq!(
+            snapshot.to_json(),
+            r#"{"fraction":null,"rate":null,"eta":null,"elapsed":0.0,"message":"\u0001\n"}"#
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Snapshot::default().to_string(), "0ms elapsed");
+        let snapshot = Snapshot {
+            fraction: Some(f32::NAN),
+            rate: Some(0.0),
+            eta: Some(Duration::from_secs(90)),
+            elapsed: Duration::from_secs(30),
+            message: "",
+        };
+        assert_eq!(snapshot.to_string(), "0.00 /s, 1m 30s left, 30s elapsed");
+    }
+
+    #[test]
+    fn capture_unknown() {
+        let throughput = Throughput::new(0.5, Duration::from_secs(1));
+        let eta = Eta::new(::estimate::Kalman::new(1e-4, 1e-2));
+        assert_eq!(
+            Snapshot::capture(&throughput, &eta, Duration::from_secs(0), "idle"),
+            Snapshot {
+                message: "idle",
+                ..Snapshot::default()
+            }
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let snapshot = Snapshot {
+            fraction: Some(0.25),
+            eta: Some(Duration::from_millis(1500)),
+            message: "a\"b",
+            ..Snapshot::default()
+        };
+        assert_eq!(
+            ::serde_json::to_string(&snapshot).unwrap(),
+            snapshot.to_json()
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Constant-memory summaries of a stream of samples, e.g. per-item latency or instantaneous throughput
+
+use std::fmt::{self, Display};
+
+use prefix;
+
+/// Running count, minimum, maximum, mean, and most recent value of a series of samples
+///
+/// # Examples
+/// ```
+/// # use yapb::stats::Stats;
+/// let mut stats = Stats::new();
+/// stats.update(2.0 * 1024.0 * 1024.0);
+/// stats.update(6.4 * 1024.0 * 1024.0);
+/// assert_eq!(format!("{}", stats.binary("B/s")), "avg 4.20 MiB/s, peak 6.40 MiB/s");
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Stats {
+    count: u64,
+    min: f64,
+    max: f64,
+    mean: f64,
+    last: f64,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        // Incremental form avoids the loss of precision of summing many samples before dividing
+        self.mean += (value - self.mean) / self.count as f64;
+        self.last = value;
+    }
+
+    /// Number of samples seen
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.get(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.get(self.max)
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        self.get(self.mean)
+    }
+
+    /// The most recent sample
+    pub fn last(&self) -> Option<f64> {
+        self.get(self.last)
+    }
+
+    /// Forget all samples
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Display the mean and maximum in `unit`s using binary prefixes, e.g. `avg 4.20 MiB/s, peak 11.0 MiB/s`
+    ///
+    /// Displays nothing if no samples have been seen.
+    pub fn binary<'a>(&self, unit: &'a str) -> impl Display + 'a {
+        Summary {
+            stats: *self,
+            unit,
+            binary: true,
+        }
+    }
+
+    /// Display the mean and maximum in `unit`s using SI prefixes
+    ///
+    /// Displays nothing if no samples have been seen.
+    pub fn scientific<'a>(&self, unit: &'a str) -> impl Display + 'a {
+        Summary {
+            stats: *self,
+            unit,
+            binary: false,
+        }
+    }
+
+    fn get(&self, x: f64) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(x)
+        }
+    }
+}
+
+struct Summary<'a> {
+    stats: Stats,
+    unit: &'a str,
+    binary: bool,
+}
+
+impl<'a> Display for Summary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.stats.count == 0 {
+            return Ok(());
+        }
+        if self.binary {
+            write!(
+                f,
+                "avg {}{}, peak {}{}",
+                prefix::Binary(self.stats.mean),
+                self.unit,
+                prefix::Binary(self.stats.max),
+                self.unit
+            )
+        } else {
+            write!(
+                f,
+                "avg {}{}, peak {}{}",
+                prefix::Scientific(self.stats.mean),
+                self.unit,
+                prefix::Scientific(self.stats.max),
+                self.unit
+            )
+        }
+    }
+}
+
+/// Streaming estimate of a quantile, e.g. the median or 95th percentile latency, in constant memory
+///
+/// Implements the P² algorithm of Jain and Chlamtac, which maintains five markers whose heights are adjusted by
+/// piecewise-parabolic interpolation as samples arrive. The result is exact for the first five samples and converges
+/// quickly for reasonably smooth distributions.
+///
+/// # Examples
+/// ```
+/// # use yapb::stats::Quantile;
+/// let mut p95 = Quantile::new(0.95);
+/// for i in 0..1000 {
+///     p95.update(f64::from((i * 7919) % 1000));
+/// }
+/// let estimate = p95.get().unwrap();
+/// assert!((estimate - 950.0).abs() < 10.0);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Quantile {
+    p: f64,
+    count: u64,
+    /// Marker heights, i.e. the estimated values at each marker
+    heights: [f64; 5],
+    /// Actual marker positions, counting samples from 1
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl Quantile {
+    /// Estimate the `p`th quantile, where `p` is in [0, 1]
+    ///
+    /// # Panics
+    /// If `p` is outside [0, 1].
+    pub fn new(p: f64) -> Self {
+        assert!((0.0..=1.0).contains(&p), "p must be in [0, 1]");
+        Self {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    /// Incorporate a sample
+    ///
+    /// NaN samples are ignored, since they have no place in the ordering the quantile is drawn from.
+    pub fn update(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(|a, b| a.total_cmp(b));
+            }
+            return;
+        }
+        self.count += 1;
+
+        let q = &mut self.heights;
+        let k = if value < q[0] {
+            q[0] = value;
+            0
+        } else if value >= q[4] {
+            q[4] = value;
+            3
+        } else {
+            (0..4).find(|&i| value < q[i + 1]).unwrap()
+        };
+        for n in &mut self.positions[k + 1..] {
+            *n += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(&self.increments) {
+            *desired += increment;
+        }
+
+        let n = &mut self.positions;
+        for i in 1..4 {
+            let d = self.desired[i] - n[i];
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    /// The estimated quantile, or `None` if no samples have been seen
+    pub fn get(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            n if n < 5 => {
+                let mut sorted = [0.0; 5];
+                let sorted = &mut sorted[..n as usize];
+                sorted.copy_from_slice(&self.heights[..n as usize]);
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                Some(sorted[(self.p * (n - 1) as f64).round() as usize])
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    /// Number of samples seen
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The quantile being estimated
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let mut stats = Stats::new();
+        assert_eq!(stats.mean(), None);
+        assert_eq!(format!("{}", stats.scientific("s")), "");
+        for &x in &[3.0, -1.0, 4.0, 2.0] {
+            stats.update(x);
+        }
+        assert_eq!(stats.count(), 4);
+        assert_eq!(stats.min(), Some(-1.0));
+        assert_eq!(stats.max(), Some(4.0));
+        assert_eq!(stats.mean(), Some(2.0));
+        assert_eq!(stats.last(), Some(2.0));
+    }
+
+    #[test]
+    fn median() {
+        let mut median = Quantile::new(0.5);
+        assert_eq!(median.get(), None);
+        for &x in &[5.0, 1.0, 3.0] {
+            median.update(x);
+        }
+        assert_eq!(median.get(), Some(3.0));
+        for i in 0..10_000 {
+            median.update(f64::from((i * 7919) % 10_000));
+        }
+        assert!((median.get().unwrap() - 5000.0).abs() < 100.0);
+    }
+
+    #[test]
+    fn quantile_nan() {
+        let mut max = Quantile::new(1.0);
+        for &x in &[2.0, f64::NAN, 1.0] {
+            max.update(x);
+        }
+        assert_eq!(max.count(), 2);
+        assert_eq!(max.get(), Some(2.0));
+        for i in 0..10 {
+            max.update(f64::from(i));
+            max.update(f64::NAN);
+        }
+        assert_eq!(max.count(), 12);
+        assert_eq!(max.get().map(f64::is_nan), Some(false));
+    }
+}
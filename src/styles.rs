@@ -0,0 +1,211 @@
+//! Metadata describing the built-in spinners, so applications can list styles or pick ones suited to the terminal
+
+use {AnySpinner, Spinner, SpinnerStyle};
+
+/// The character repertoire an output device must support to render a style
+///
+/// Variants are ordered from least to most demanding, and each is assumed to imply support for those before it.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub enum Charset {
+    /// 7-bit ASCII
+    Ascii,
+    /// Code page 437, as found on legacy Windows consoles and BIOS-style serial terminals
+    Cp437,
+    /// Unicode block elements, arrows, and geometric shapes
+    Unicode,
+    /// Unicode braille patterns, which some otherwise-capable fonts lack
+    Braille,
+    /// Emoji, which most terminals render two cells wide
+    Emoji,
+    /// Symbols for Legacy Computing, e.g. the sextants used by `legacy::Canvas`, which few fonts cover yet
+    Legacy,
+}
+
+/// Description of a built-in spinner
+///
+/// Only what can't be read off the spinner itself is stored; `name` and `frames` are derived from `style`.
+#[derive(Debug, Copy, Clone)]
+pub struct StyleInfo {
+    pub style: SpinnerStyle,
+    /// Number of terminal cells occupied by each frame
+    pub width: usize,
+    /// The least capable character repertoire that can render every frame
+    pub charset: Charset,
+}
+
+impl StyleInfo {
+    /// Name accepted by `SpinnerStyle::from_str`
+    pub fn name(&self) -> &'static str {
+        self.style.name()
+    }
+
+    /// Number of states in one full cycle of the animation
+    pub fn frames(&self) -> u32 {
+        AnySpinner::new(self.style).frames()
+    }
+}
+
+/// Every built-in spinner, in the same order as `SpinnerStyle::ALL`
+pub const STYLES: &[StyleInfo] = &[
+    StyleInfo {
+        style: SpinnerStyle::Line,
+        width: 1,
+        charset: Charset::Ascii,
+    },
+    StyleInfo {
+        style: SpinnerStyle::HalfBlock,
+        width: 1,
+        charset: Charset::Cp437,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Scanner,
+        width: 8,
+        charset: Charset::Cp437,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Counter16,
+        width: 1,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Spinner4,
+        width: 1,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Arrow,
+        width: 1,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Trail,
+        width: 1,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Bounce,
+        width: 8,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Wave,
+        width: 16,
+        charset: Charset::Unicode,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Counter256,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Spinner8,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Snake,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Dots,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Dots2,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Dots3,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::Dissolve,
+        width: 1,
+        charset: Charset::Braille,
+    },
+    StyleInfo {
+        style: SpinnerStyle::WideSnake,
+        width: 4,
+        charset: Charset::Braille,
+    },
+    #[cfg(feature = "emoji")]
+    StyleInfo {
+        style: SpinnerStyle::Moon,
+        width: 2,
+        charset: Charset::Emoji,
+    },
+    #[cfg(feature = "emoji")]
+    StyleInfo {
+        style: SpinnerStyle::Clock,
+        width: 2,
+        charset: Charset::Emoji,
+    },
+    #[cfg(feature = "emoji")]
+    StyleInfo {
+        style: SpinnerStyle::Hourglass,
+        width: 2,
+        charset: Charset::Emoji,
+    },
+    #[cfg(feature = "emoji")]
+    StyleInfo {
+        style: SpinnerStyle::Globe,
+        width: 2,
+        charset: Charset::Emoji,
+    },
+    #[cfg(feature = "emoji")]
+    StyleInfo {
+        style: SpinnerStyle::Weather,
+        width: 2,
+        charset: Charset::Emoji,
+    },
+];
+
+/// Look up the description of `style`
+pub fn info(style: SpinnerStyle) -> &'static StyleInfo {
+    STYLES.iter().find(|x| x.style == style).unwrap()
+}
+
+/// Styles that can be rendered by a device supporting `charset`
+///
+/// # Examples
+/// ```
+/// # use yapb::styles::*;
+/// for style in compatible(Charset::Unicode) {
+///     println!("{} ({} frames)", style.name(), style.frames());
+/// }
+/// ```
+pub fn compatible(charset: Charset) -> impl Iterator<Item = &'static StyleInfo> {
+    STYLES.iter().filter(move |x| x.charset <= charset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use width::str_width;
+
+    #[test]
+    fn consistent() {
+        assert_eq!(STYLES.len(), SpinnerStyle::ALL.len());
+        for (info, &style) in STYLES.iter().zip(SpinnerStyle::ALL) {
+            assert_eq!(info.style, style);
+            let spinner = AnySpinner::new(style);
+            for frame in spinner.iter_frames() {
+                assert_eq!(str_width(&frame), info.width);
+                if info.charset == Charset::Ascii {
+                    assert!(frame.is_ascii());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordered() {
+        for pair in STYLES.windows(2) {
+            assert!((pair[0].charset, pair[0].width) <= (pair[1].charset, pair[1].width));
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! SVG renderings of progress, for embedding snapshots in reports and READMEs
+
+use std::fmt::{self, Display, Write};
+
+use html::Escaped;
+
+/// Dimensions and colors of rendered images
+///
+/// # Examples
+/// ```
+/// # use yapb::svg::Canvas;
+/// let canvas = Canvas { width: 100.0, height: 10.0, ..Canvas::default() };
+/// assert_eq!(
+///     canvas.bar(0.25).to_string(),
+///     "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"10\" viewBox=\"0 0 100 10\">\
+///      <rect width=\"100\" height=\"10\" fill=\"#e0e0e0\"/>\
+///      <rect width=\"25\" height=\"10\" fill=\"#4caf50\"/></svg>"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Canvas<'a> {
+    pub width: f32,
+    pub height: f32,
+    /// CSS color of completed progress, or of the line in a sparkline
+    pub fill: &'a str,
+    /// CSS color of remaining progress
+    pub background: &'a str,
+}
+
+impl<'a> Default for Canvas<'a> {
+    fn default() -> Self {
+        Self {
+            width: 120.0,
+            height: 12.0,
+            fill: "#4caf50",
+            background: "#e0e0e0",
+        }
+    }
+}
+
+impl<'a> Canvas<'a> {
+    /// A horizontal bar filled to `fraction`
+    pub fn bar(&self, fraction: f32) -> impl Display + 'a {
+        Image {
+            canvas: *self,
+            kind: Kind::Bar(fraction.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// A ring filled clockwise from the top to `fraction`, centered in the canvas
+    pub fn gauge(&self, fraction: f32) -> impl Display + 'a {
+        Image {
+            canvas: *self,
+            kind: Kind::Gauge(fraction.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// A line through `values`, scaled to fill the canvas between their minimum and maximum
+    pub fn sparkline<'b>(&self, values: &'b [f32]) -> impl Display + 'b
+    where
+        'a: 'b,
+    {
+        Image {
+            canvas: *self,
+            kind: Kind::Sparkline(values),
+        }
+    }
+}
+
+struct Image<'a> {
+    canvas: Canvas<'a>,
+    kind: Kind<'a>,
+}
+
+enum Kind<'a> {
+    Bar(f32),
+    Gauge(f32),
+    Sparkline(&'a [f32]),
+}
+
+impl<'a> Display for Image<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Canvas {
+            width,
+            height,
+            fill,
+            background,
+        } = self.canvas;
+        let (w, h) = (Num(width), Num(height));
+        write!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        )?;
+        match self.kind {
+            Kind::Bar(fraction) => {
+                write!(
+                    f,
+                    r#"<rect width="{w}" height="{h}" fill="{}"/>"#,
+                    Escaped(background)
+                )?;
+                write!(
+                    f,
+                    r#"<rect width="{}" height="{h}" fill="{}"/>"#,
+                    Num(width * fraction),
+                    Escaped(fill)
+                )?;
+            }
+            Kind::Gauge(fraction) => {
+                let stroke = width.min(height) * 0.15;
+                let radius = (width.min(height) - stroke) / 2.0;
+                let circumference = 2.0 * std::f32::consts::PI * radius;
+                let circle = format!(
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke-width="{}""#,
+                    Num(width / 2.0),
+                    Num(height / 2.0),
+                    Num(radius),
+                    Num(stroke)
+                );
+                write!(f, r#"{} stroke="{}"/>"#, circle, Escaped(background))?;
+                // Dash the ring's outline to the filled length, starting from the top
+                write!(
+                    f,
+                    r#"{} stroke="{}" stroke-dasharray="{} {}" transform="rotate(-90 {} {})"/>"#,
+                    circle,
+                    Escaped(fill),
+                    Num(circumference * fraction),
+                    Num(circumference),
+                    Num(width / 2.0),
+                    Num(height / 2.0)
+                )?;
+            }
+            Kind::Sparkline(values) => {
+                let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+                let max = values.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+                let step = width / (values.len().max(2) - 1) as f32;
+                f.write_str(r#"<polyline fill="none" points=""#)?;
+                for (i, &x) in values.iter().enumerate() {
+                    if i != 0 {
+                        f.write_char(' ')?;
+                    }
+                    let level = if max > min {
+                        (x - min) / (max - min)
+                    } else {
+                        0.5
+                    };
+                    write!(
+                        f,
+                        "{},{}",
+                        Num(i as f32 * step),
+                        Num(height * (1.0 - level))
+                    )?;
+                }
+                write!(f, r#"" stroke="{}"/>"#, Escaped(fill))?;
+            }
+        }
+        f.write_str("</svg>")
+    }
+}
+
+/// A coordinate, rounded to two decimal places without trailing zeroes
+struct Num(f32);
+
+impl Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = format!("{:.2}", self.0);
+        let s = s.trim_end_matches('0').trim_end_matches('.');
+        f.write_str(if s == "-0" { "0" } else { s })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        let canvas = Canvas {
+            width: 20.0,
+            height: 10.0,
+            fill: "red",
+            background: "none",
+        };
+        let header = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\" viewBox=\"0 0 20 10\">";
+        assert_eq!(
+            canvas.sparkline(&[1.0, 3.0, 2.0]).to_string(),
+            format!(
+                "{}<polyline fill=\"none\" points=\"0,10 10,0 20,5\" stroke=\"red\"/></svg>",
+                header
+            )
+        );
+        assert_eq!(
+            canvas.gauge(0.5).to_string(),
+            format!(
+                "{}<circle cx=\"10\" cy=\"5\" r=\"4.25\" fill=\"none\" stroke-width=\"1.5\" stroke=\"none\"/>\
+                 <circle cx=\"10\" cy=\"5\" r=\"4.25\" fill=\"none\" stroke-width=\"1.5\" stroke=\"red\" \
+                 stroke-dasharray=\"13.35 26.7\" transform=\"rotate(-90 10 5)\"/></svg>",
+                header
+            )
+        );
+        assert_eq!(Num(0.125).to_string(), "0.12");
+        assert_eq!(Num(-0.001).to_string(), "0");
+    }
+}
@@ -0,0 +1,518 @@
+//! Rendering of whole status lines from a format string with named placeholders
+//!
+//! Like the rest of this crate, templates perform no IO: rendering produces text, which the caller is responsible for
+//! writing wherever it is needed.
+//!
+//! # Examples
+//! ```
+//! # use yapb::*;
+//! # use yapb::template::Template;
+//! let template = Template::parse("{spinner} [{bar:10}] {percent:>4}").unwrap();
+//! let mut bar = Bar::new();
+//! bar.set(0.5);
+//! let line = template.render(&[("spinner", &Line::new()), ("bar", &bar), ("percent", &"50%")]);
+//! assert_eq!(line, "| [█████     ]  50%");
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use duration::{Hms, Human};
+use prefix::{ByteSize, Convention, ExactByteSize, Grouped, Rate};
+use snapshot::Snapshot;
+use width;
+use {Bar, Progress};
+
+/// A parsed format string, e.g. `{spinner} {bar:40} {percent} ETA {eta}`
+///
+/// Placeholders consist of a name made of ASCII letters, digits, and underscores, optionally followed by a colon, an
+/// alignment of `<`, `^`, or `>`, and a width. The width is passed to the bound value's `Display` implementation, so
+/// widgets such as `Bar` use it as their length. Literal braces are written `{{` and `}}`.
+///
+/// For compatibility with indicatif's `ProgressStyle`, the width may be followed by `!` to truncate values that would
+/// exceed it, and the spec may end with a style such as `.cyan/blue`, which is ignored. See `Indicatif` for rendering
+/// indicatif's built-in placeholders.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Placeholder {
+    name: String,
+    align: Option<Align>,
+    width: Option<usize>,
+    truncate: bool,
+}
+
+/// Equivalent to `fmt::Alignment`, which lacks `Eq`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Template {
+    /// Parse a format string
+    pub fn parse(s: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|x| x.1) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().map(|x| x.1) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(TemplateError::Unmatched { position }),
+                '{' => {
+                    let start = position + 1;
+                    let end = loop {
+                        match chars.next() {
+                            Some((i, '}')) => break i,
+                            Some(_) => {}
+                            None => return Err(TemplateError::Unclosed { position }),
+                        }
+                    };
+                    let placeholder = parse_placeholder(&s[start..end])
+                        .ok_or(TemplateError::Invalid { position })?;
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(literal.split_off(0)));
+                    }
+                    segments.push(Segment::Placeholder(placeholder));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Names of the placeholders, in order of appearance
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|x| match *x {
+            Segment::Placeholder(ref p) => Some(&p.name[..]),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Render the template, substituting each placeholder with the value bound to its name
+    ///
+    /// Placeholders with no bound value are rendered as nothing.
+    pub fn render(&self, values: &[(&str, &dyn Display)]) -> String {
+        self.display(values).to_string()
+    }
+
+    /// Display the template, substituting each placeholder with the value bound to its name
+    ///
+    /// Placeholders with no bound value are rendered as nothing.
+    pub fn display<'a>(&'a self, values: &'a [(&'a str, &'a dyn Display)]) -> impl Display + 'a {
+        Rendered {
+            template: self,
+            values,
+        }
+    }
+}
+
+fn parse_placeholder(s: &str) -> Option<Placeholder> {
+    let (name, spec) = match s.find(':') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    };
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let (align, width) = match spec.chars().next() {
+        Some('<') => (Some(Align::Left), &spec[1..]),
+        Some('^') => (Some(Align::Center), &spec[1..]),
+        Some('>') => (Some(Align::Right), &spec[1..]),
+        _ => (None, spec),
+    };
+    // Styles are only meaningful to indicatif, which renders them as ANSI escapes
+    let width = match width.find('.') {
+        Some(i) if i + 1 < width.len() => &width[..i],
+        Some(_) => return None,
+        None => width,
+    };
+    let (width, truncate) = match width.strip_suffix('!') {
+        Some(x) => (x, true),
+        None => (width, false),
+    };
+    let width = if width.is_empty() {
+        None
+    } else {
+        Some(width.parse().ok()?)
+    };
+    if truncate && width.is_none() {
+        return None;
+    }
+    Some(Placeholder {
+        name: name.into(),
+        align,
+        width,
+        truncate,
+    })
+}
+
+struct Rendered<'a> {
+    template: &'a Template,
+    values: &'a [(&'a str, &'a dyn Display)],
+}
+
+impl<'a> Display for Rendered<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for segment in &self.template.segments {
+            let placeholder = match *segment {
+                Segment::Literal(ref s) => {
+                    f.write_str(s)?;
+                    continue;
+                }
+                Segment::Placeholder(ref p) => p,
+            };
+            let value = match self.values.iter().find(|x| x.0 == placeholder.name) {
+                Some(&(_, value)) => value,
+                None => continue,
+            };
+            f.write_str(&placeholder.format(value, None))?;
+        }
+        Ok(())
+    }
+}
+
+impl Placeholder {
+    /// Format `value` as if by this placeholder's spec, with `width` in place of the spec's own if supplied
+    fn format(&self, value: &dyn Display, width: Option<usize>) -> String {
+        let width = match width.or(self.width) {
+            None => return value.to_string(),
+            Some(x) => x,
+        };
+        let s = match self.align {
+            None => format!("{:1$}", value, width),
+            Some(Align::Left) => format!("{:<1$}", value, width),
+            Some(Align::Center) => format!("{:^1$}", value, width),
+            Some(Align::Right) => format!("{:>1$}", value, width),
+        };
+        if self.truncate {
+            width::truncate(&s, width).into()
+        } else {
+            s
+        }
+    }
+}
+
+/// Progress state from which the placeholders built into indicatif's `ProgressStyle` are rendered
+///
+/// This allows projects to migrate from indicatif without rewriting their templates. Supported placeholders are
+/// `bar`, `wide_bar`, `spinner`, `prefix`, `msg`, `wide_msg`, `pos`, `len`, `human_pos`, `human_len`, `percent`,
+/// `percent_precise`, `bytes`, `total_bytes`, `binary_bytes`, `binary_total_bytes`, `decimal_bytes`,
+/// `decimal_total_bytes`, `elapsed`, `elapsed_precise`, `eta`, `eta_precise`, `duration`, `duration_precise`,
+/// `per_sec`, `bytes_per_sec`, `binary_bytes_per_sec`, and `decimal_bytes_per_sec`. Values are rendered with this
+/// crate's formatters, so e.g. durations read `3m 12s` rather than `3 minutes`. The ETA and total duration are
+/// rendered as nothing when no estimate can be made.
+///
+/// # Examples
+/// ```
+/// # use yapb::template::{Indicatif, Template};
+/// # use std::time::Duration;
+/// let template = Template::parse("{msg} [{bar:10.cyan/blue}] {bytes}/{total_bytes} ({eta})").unwrap();
+/// let state = Indicatif {
+///     pos: 512 * 1024,
+///     len: Some(1024 * 1024),
+///     elapsed: Duration::from_secs(10),
+///     message: "copying",
+///     ..Indicatif::default()
+/// };
+/// assert_eq!(state.render(&template), "copying [█████     ] 512 KiB/1.00 MiB (10s)");
+/// ```
+#[derive(Copy, Clone)]
+pub struct Indicatif<'a> {
+    /// Units of work completed
+    pub pos: u64,
+    /// Total units of work, if known
+    pub len: Option<u64>,
+    /// Time since work began
+    pub elapsed: Duration,
+    /// Estimated time remaining, if a better estimate than extrapolating from `pos` and `elapsed` is available
+    pub eta: Option<Duration>,
+    /// Units of work completed per second, if a better estimate than `pos / elapsed` is available
+    pub per_sec: Option<f64>,
+    pub message: &'a str,
+    pub prefix: &'a str,
+    pub spinner: Option<&'a dyn Display>,
+    /// Width of the line in terminal cells, which `wide_bar` and `wide_msg` expand to fill
+    pub width: usize,
+}
+
+impl<'a> Default for Indicatif<'a> {
+    fn default() -> Self {
+        Self {
+            pos: 0,
+            len: None,
+            elapsed: Duration::from_secs(0),
+            eta: None,
+            per_sec: None,
+            message: "",
+            prefix: "",
+            spinner: None,
+            width: 80,
+        }
+    }
+}
+
+impl<'a> Indicatif<'a> {
+    /// Render `template`, substituting indicatif's placeholders
+    ///
+    /// Unrecognized placeholders are rendered as nothing.
+    pub fn render(&self, template: &Template) -> String {
+        let mut parts = template
+            .segments
+            .iter()
+            .map(|segment| match *segment {
+                Segment::Literal(ref s) => Some(s.clone()),
+                Segment::Placeholder(ref p) if is_wide(&p.name) => None,
+                Segment::Placeholder(ref p) => Some(self.value(p, None)),
+            })
+            .collect::<Vec<_>>();
+        let used = parts
+            .iter()
+            .flatten()
+            .map(|x| width::str_width(x))
+            .sum::<usize>();
+        let mut wide = parts.iter().filter(|x| x.is_none()).count();
+        let mut remaining = self.width.saturating_sub(used);
+        let mut line = String::new();
+        for (part, segment) in parts.iter_mut().zip(&template.segments) {
+            match (part.take(), segment) {
+                (Some(x), _) => line.push_str(&x),
+                (None, Segment::Placeholder(p)) => {
+                    // Divide the space evenly, giving any excess to the earliest
+                    let share = remaining.div_ceil(wide);
+                    remaining -= share;
+                    wide -= 1;
+                    line.push_str(&self.value(p, Some(share)));
+                }
+                (None, Segment::Literal(_)) => unreachable!(),
+            }
+        }
+        line
+    }
+
+    /// Machine-readable form of the state, with rate and ETA derived as for rendering
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use yapb::template::Indicatif;
+    /// let state = Indicatif {
+    ///     pos: 25,
+    ///     len: Some(100),
+    ///     elapsed: Duration::from_secs(5),
+    ///     message: "copying",
+    ///     ..Indicatif::default()
+    /// };
+    /// assert_eq!(
+    ///     state.snapshot().to_json(),
+    ///     r#"{"fraction":0.25,"rate":5.0,"eta":15.0,"elapsed":5.0,"message":"copying"}"#
+    /// );
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<'a> {
+        Snapshot {
+            fraction: self.len.map(|_| self.fraction() as f32),
+            rate: Some(self.per_sec()),
+            eta: self.eta.or_else(|| self.estimate_eta()),
+            elapsed: self.elapsed,
+            message: self.message,
+        }
+    }
+
+    fn fraction(&self) -> f64 {
+        match self.len {
+            Some(0) => 1.0,
+            Some(len) => self.pos as f64 / len as f64,
+            None => 0.0,
+        }
+    }
+
+    fn per_sec(&self) -> f64 {
+        let elapsed = self.elapsed.as_secs_f64();
+        self.per_sec.unwrap_or(if elapsed > 0.0 {
+            self.pos as f64 / elapsed
+        } else {
+            0.0
+        })
+    }
+
+    /// Time remaining by extrapolating from the rate, if possible
+    fn estimate_eta(&self) -> Option<Duration> {
+        let per_sec = self.per_sec();
+        match self.len {
+            // A tiny rate can make the estimate too large to represent
+            Some(len) if per_sec > 0.0 => {
+                Duration::try_from_secs_f64(len.saturating_sub(self.pos) as f64 / per_sec).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn value(&self, p: &Placeholder, width: Option<usize>) -> String {
+        let fraction = self.fraction();
+        let per_sec = self.per_sec();
+        let eta = self.eta.or_else(|| self.estimate_eta());
+        let duration = eta.and_then(|x| self.elapsed.checked_add(x));
+        let len = self.len.unwrap_or(0);
+        let mut bar = Bar::new();
+        bar.set(fraction as f32);
+        let bytes = |x, convention| ExactByteSize(u128::from(x), convention);
+        let rate = |convention| format!("{}/s", ByteSize(per_sec, convention));
+        match &p.name[..] {
+            "bar" => p.format(&bar, width.or(p.width).or(Some(20))),
+            "wide_bar" => p.format(&bar, width),
+            "spinner" => self.spinner.map_or(String::new(), |x| p.format(x, width)),
+            "prefix" => p.format(&self.prefix, width),
+            "msg" => p.format(&self.message, width),
+            "wide_msg" => {
+                let width = width.unwrap_or(0);
+                let s = p.format(&self.message, Some(width));
+                width::truncate(&s, width).into()
+            }
+            "pos" => p.format(&self.pos, width),
+            "len" => p.format(&len, width),
+            "human_pos" => p.format(&Grouped(self.pos, ','), width),
+            "human_len" => p.format(&Grouped(len, ','), width),
+            "percent" => p.format(&((fraction * 100.0).floor() as u32), width),
+            "percent_precise" => p.format(&format!("{:.3}", fraction * 100.0), width),
+            "bytes" | "binary_bytes" => p.format(&bytes(self.pos, Convention::Binary), width),
+            "total_bytes" | "binary_total_bytes" => {
+                p.format(&bytes(len, Convention::Binary), width)
+            }
+            "decimal_bytes" => p.format(&bytes(self.pos, Convention::Si), width),
+            "decimal_total_bytes" => p.format(&bytes(len, Convention::Si), width),
+            "elapsed" => p.format(&Human(self.elapsed), width),
+            "elapsed_precise" => p.format(&Hms(self.elapsed), width),
+            "eta" => eta.map_or(String::new(), |x| p.format(&Human(x), width)),
+            "eta_precise" => eta.map_or(String::new(), |x| p.format(&Hms(x), width)),
+            "duration" => duration.map_or(String::new(), |x| p.format(&Human(x), width)),
+            "duration_precise" => duration.map_or(String::new(), |x| p.format(&Hms(x), width)),
+            "per_sec" => p.format(&Rate(per_sec, ""), width),
+            "bytes_per_sec" | "binary_bytes_per_sec" => p.format(&rate(Convention::Binary), width),
+            "decimal_bytes_per_sec" => p.format(&rate(Convention::Si), width),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Whether a placeholder expands to fill the remaining width
+fn is_wide(name: &str) -> bool {
+    name == "wide_bar" || name == "wide_msg"
+}
+
+/// Reasons a format string can't be parsed as a `Template`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum TemplateError {
+    /// A `{` has no matching `}`
+    Unclosed {
+        /// Byte offset of the `{`
+        position: usize,
+    },
+    /// A `}` has no matching `{`, and isn't escaped as `}}`
+    Unmatched {
+        /// Byte offset of the `}`
+        position: usize,
+    },
+    /// A placeholder's name or format spec is malformed
+    Invalid {
+        /// Byte offset of the placeholder's `{`
+        position: usize,
+    },
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TemplateError::Unclosed { position } => {
+                write!(f, "unclosed `{{` at offset {}", position)
+            }
+            TemplateError::Unmatched { position } => {
+                write!(f, "unmatched `}}` at offset {}", position)
+            }
+            TemplateError::Invalid { position } => {
+                write!(f, "invalid placeholder at offset {}", position)
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Template::parse("{bar"),
+            Err(TemplateError::Unclosed { position: 0 })
+        );
+        assert_eq!(
+            Template::parse("a}b"),
+            Err(TemplateError::Unmatched { position: 1 })
+        );
+        assert_eq!(
+            Template::parse("x {} y"),
+            Err(TemplateError::Invalid { position: 2 })
+        );
+        assert_eq!(
+            Template::parse("{bar:wide}"),
+            Err(TemplateError::Invalid { position: 0 })
+        );
+    }
+
+    #[test]
+    fn render() {
+        let template = Template::parse("{{{a:^5}}} {b} {missing}").unwrap();
+        assert_eq!(template.names().collect::<Vec<_>>(), ["a", "b", "missing"]);
+        assert_eq!(template.render(&[("a", &1), ("b", &"two")]), "{  1  } two ");
+    }
+
+    #[test]
+    fn indicatif() {
+        let template = Template::parse("{prefix:>4!} {wide_msg} [{wide_bar}] {pos}/{len}").unwrap();
+        let state = Indicatif {
+            pos: 1,
+            len: Some(4),
+            prefix: "prefix",
+            message: "hello",
+            width: 30,
+            ..Indicatif::default()
+        };
+        assert_eq!(state.render(&template), "pref hello     [██▎      ] 1/4");
+        assert_eq!(width::str_width(&state.render(&template)), 30);
+    }
+
+    #[test]
+    fn indicatif_slow() {
+        let state = Indicatif {
+            len: Some(4),
+            per_sec: Some(1e-300),
+            ..Indicatif::default()
+        };
+        assert_eq!(state.snapshot().eta, None);
+        let template = Template::parse("[{eta}] [{duration_precise}]").unwrap();
+        assert_eq!(state.render(&template), "[] []");
+    }
+}
@@ -0,0 +1,451 @@
+//! Colors shared by indicators, so an application can restyle all of them consistently
+//!
+//! Terminal output is colored with ANSI SGR escape sequences, and graphical renderers such as `sixel` take their
+//! colors from the same `Theme`. Themes can be reduced to what the output device supports with `Theme::degrade`,
+//! given a `Tier` chosen by the application or, with the `detect` feature, by `Tier::detect`.
+
+use std::fmt::{self, Display, Write};
+
+use styles::Charset;
+use {Bar, BarStyle};
+
+/// A color as understood by terminals
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum Color {
+    /// The terminal's default color, i.e. no escape sequence at all
+    #[default]
+    Default,
+    /// One of the 16 basic colors, with 8-15 being the bright variants of 0-7
+    Ansi(u8),
+    /// A color from the 256-color palette
+    Indexed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Approximate RGB value, taking the basic colors from xterm's defaults, or `None` for `Color::Default`
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::theme::Color;
+    /// assert_eq!(Color::Indexed(196).rgb(), Some((255, 0, 0)));
+    /// assert_eq!(Color::Indexed(244).rgb(), Some((128, 128, 128)));
+    /// assert_eq!(Color::Default.rgb(), None);
+    /// ```
+    pub fn rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Default => None,
+            Color::Ansi(n) => Some(ANSI_RGB[usize::from(n % 16)]),
+            Color::Indexed(n @ 0..=15) => Some(ANSI_RGB[usize::from(n)]),
+            Color::Indexed(n @ 16..=231) => {
+                let level = |x: u8| if x == 0 { 0 } else { 55 + 40 * x };
+                let n = n - 16;
+                Some((level(n / 36), level(n / 6 % 6), level(n % 6)))
+            }
+            Color::Indexed(n) => {
+                let level = 8 + 10 * (n - 232);
+                Some((level, level, level))
+            }
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+        }
+    }
+
+    /// The closest color that can be shown by a device supporting `tier`
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::theme::{Color, Tier};
+    /// let orange = Color::Rgb(0xff, 0x80, 0x00);
+    /// assert_eq!(orange.degrade(Tier::Ansi256), Color::Indexed(208));
+    /// assert_eq!(orange.degrade(Tier::Ansi16), Color::Ansi(3));
+    /// assert_eq!(orange.degrade(Tier::Mono), Color::Default);
+    /// ```
+    pub fn degrade(self, tier: Tier) -> Color {
+        let rgb = match self.rgb() {
+            Some(x) => x,
+            None => return self,
+        };
+        match (tier, self) {
+            (Tier::TrueColor, _)
+            | (Tier::Ansi256, Color::Indexed(_))
+            | (Tier::Ansi16, Color::Ansi(_)) => self,
+            (Tier::Ansi256, _) => Color::Indexed(nearest(16..=255, rgb)),
+            (Tier::Ansi16, _) => Color::Ansi(nearest(0..=15, rgb)),
+            (Tier::Mono, _) | (Tier::Ascii, _) => Color::Default,
+        }
+    }
+
+    /// Write `value` in this color, restoring the default afterwards
+    ///
+    /// The formatter's width and fill are passed through to `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::theme::Color;
+    /// assert_eq!(format!("{:3}", Color::Ansi(1).paint("x")), "\x1b[31mx  \x1b[39m");
+    /// assert_eq!(Color::Indexed(208).paint("x").to_string(), "\x1b[38;5;208mx\x1b[39m");
+    /// assert_eq!(Color::Default.paint("x").to_string(), "x");
+    /// ```
+    pub fn paint<T: Display>(self, value: T) -> Paint<T> {
+        Paint(self, value)
+    }
+
+    /// Write the escape sequence selecting this color for the foreground
+    fn set(self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Default => Ok(()),
+            Color::Ansi(n @ 0..=7) => write!(f, "\x1b[{}m", 30 + n),
+            Color::Ansi(n) => write!(f, "\x1b[{}m", 90 + n % 8),
+            Color::Indexed(n) => write!(f, "\x1b[38;5;{}m", n),
+            Color::Rgb(r, g, b) => write!(f, "\x1b[38;2;{};{};{}m", r, g, b),
+        }
+    }
+
+    /// Write the escape sequence restoring the default foreground, if this color changed it
+    fn reset(self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Default => Ok(()),
+            _ => f.write_str("\x1b[39m"),
+        }
+    }
+}
+
+/// xterm's default values for the 16 basic colors
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// The entry of the 256-color palette among `candidates` closest to `rgb`
+fn nearest(candidates: impl Iterator<Item = u8>, rgb: (u8, u8, u8)) -> u8 {
+    let distance = |(r, g, b): (u8, u8, u8)| {
+        let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2);
+        d(r, rgb.0) + d(g, rgb.1) + d(b, rgb.2)
+    };
+    candidates
+        .min_by_key(|&n| distance(Color::Indexed(n).rgb().unwrap()))
+        .unwrap()
+}
+
+/// A value written in a color, constructed by `Color::paint`
+#[derive(Debug, Copy, Clone)]
+pub struct Paint<T>(pub Color, pub T);
+
+impl<T: Display> Display for Paint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.set(f)?;
+        self.1.fmt(f)?;
+        self.0.reset(f)
+    }
+}
+
+/// Colors for each part of an indicator
+///
+/// # Examples
+/// ```
+/// # use yapb::*;
+/// # use yapb::theme::{Color, Theme};
+/// let theme = Theme { head: Color::Ansi(3), ..Theme::MONO };
+/// let mut bar = Bar::new();
+/// bar.set(0.55);
+/// assert_eq!(format!("[{:4}]", theme.bar(&bar)), "[██\x1b[33m▏\x1b[39m ]");
+/// assert_eq!(theme.warning("stalled").to_string(), "stalled");
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Theme {
+    /// Completed progress
+    pub filled: Color,
+    /// Remaining progress
+    pub empty: Color,
+    /// The partially filled cell at the leading edge of a bar
+    pub head: Color,
+    /// Labels and statistics
+    pub text: Color,
+    /// Conditions needing attention, such as stalls
+    pub warning: Color,
+}
+
+impl Theme {
+    /// No colors at all
+    pub const MONO: Theme = Theme {
+        filled: Color::Default,
+        empty: Color::Default,
+        head: Color::Default,
+        text: Color::Default,
+        warning: Color::Default,
+    };
+
+    /// Basic colors, which follow the terminal's own color scheme
+    pub const CLASSIC: Theme = Theme {
+        filled: Color::Ansi(2),
+        empty: Color::Ansi(8),
+        head: Color::Ansi(10),
+        text: Color::Default,
+        warning: Color::Ansi(3),
+    };
+
+    /// Blues, in 24-bit color
+    pub const OCEAN: Theme = Theme {
+        filled: Color::Rgb(0x00, 0x96, 0xc7),
+        empty: Color::Rgb(0x2b, 0x3a, 0x4a),
+        head: Color::Rgb(0x48, 0xca, 0xe4),
+        text: Color::Rgb(0xca, 0xf0, 0xf8),
+        warning: Color::Rgb(0xff, 0xb7, 0x03),
+    };
+
+    /// Oranges, in 24-bit color
+    pub const SUNSET: Theme = Theme {
+        filled: Color::Rgb(0xf7, 0x7f, 0x00),
+        empty: Color::Indexed(238),
+        head: Color::Rgb(0xfc, 0xbf, 0x49),
+        text: Color::Default,
+        warning: Color::Rgb(0xd6, 0x28, 0x28),
+    };
+
+    /// Every built-in theme, with its name
+    pub const PRESETS: &'static [(&'static str, Theme)] = &[
+        ("mono", Theme::MONO),
+        ("classic", Theme::CLASSIC),
+        ("ocean", Theme::OCEAN),
+        ("sunset", Theme::SUNSET),
+    ];
+
+    /// Look up a built-in theme by its name in `PRESETS`
+    pub fn preset(name: &str) -> Option<Theme> {
+        Theme::PRESETS
+            .iter()
+            .find(|&&(x, _)| x == name)
+            .map(|&(_, theme)| theme)
+    }
+
+    /// This theme with each color replaced by the closest that can be shown by a device supporting `tier`
+    pub fn degrade(&self, tier: Tier) -> Theme {
+        Theme {
+            filled: self.filled.degrade(tier),
+            empty: self.empty.degrade(tier),
+            head: self.head.degrade(tier),
+            text: self.text.degrade(tier),
+            warning: self.warning.degrade(tier),
+        }
+    }
+
+    /// `bar` drawn with full cells in `filled`, the leading partial cell in `head`, and empty cells in `empty`
+    ///
+    /// The bar takes its width and fill from the formatter, as usual.
+    pub fn bar<'a>(&self, bar: &'a Bar) -> impl Display + 'a {
+        ThemedBar { theme: *self, bar }
+    }
+
+    /// `value` in the `text` color
+    pub fn text<T: Display>(&self, value: T) -> Paint<T> {
+        self.text.paint(value)
+    }
+
+    /// `value` in the `warning` color
+    pub fn warning<T: Display>(&self, value: T) -> Paint<T> {
+        self.warning.paint(value)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::CLASSIC
+    }
+}
+
+/// Color and character support of an output device
+///
+/// Variants are ordered from least to most capable.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub enum Tier {
+    /// No color, and only 7-bit ASCII
+    Ascii,
+    /// No color, but full Unicode
+    Mono,
+    /// The 16 basic colors
+    Ansi16,
+    /// The 256-color palette
+    Ansi256,
+    /// 24-bit color
+    TrueColor,
+}
+
+impl Tier {
+    /// Guess the capabilities of a device from whether it's a terminal and the value of environment variables
+    ///
+    /// Output that isn't a terminal, or where `NO_COLOR` is set to a non-empty value, gets no color. A `TERM` of `dumb`
+    /// implies ASCII only. Otherwise, `COLORTERM` of `truecolor` or `24bit` and `TERM` ending in `256color` indicate
+    /// more colors than the basic 16. Where `TERM` isn't set at all, nothing is assumed beyond Unicode.
+    ///
+    /// # Examples
+    /// ```
+    /// # use yapb::theme::Tier;
+    /// let env = |name: &str| match name {
+    ///     "TERM" => Some("xterm-256color".to_string()),
+    ///     _ => None,
+    /// };
+    /// assert_eq!(Tier::from_env(true, env), Tier::Ansi256);
+    /// assert_eq!(Tier::from_env(false, env), Tier::Mono);
+    /// ```
+    pub fn from_env(is_terminal: bool, var: impl Fn(&str) -> Option<String>) -> Tier {
+        let term = var("TERM");
+        if term.as_deref() == Some("dumb") {
+            return Tier::Ascii;
+        }
+        if !is_terminal || var("NO_COLOR").is_some_and(|x| !x.is_empty()) {
+            return Tier::Mono;
+        }
+        let term = match term {
+            Some(x) => x,
+            None => return Tier::Mono,
+        };
+        match var("COLORTERM").as_deref() {
+            Some("truecolor") | Some("24bit") => Tier::TrueColor,
+            _ if term.ends_with("256color") => Tier::Ansi256,
+            _ => Tier::Ansi16,
+        }
+    }
+
+    /// Guess the capabilities of standard output from the process's environment
+    #[cfg(feature = "detect")]
+    pub fn detect() -> Tier {
+        use std::env;
+        use std::io::{self, IsTerminal};
+
+        Tier::from_env(io::stdout().is_terminal(), |name| env::var(name).ok())
+    }
+
+    /// The most demanding character repertoire safe to use, e.g. to choose spinners with `styles::compatible`
+    pub fn charset(self) -> Charset {
+        match self {
+            Tier::Ascii => Charset::Ascii,
+            _ => Charset::Braille,
+        }
+    }
+
+    /// The highest-resolution built-in bar style safe to use
+    pub fn bar_style(self) -> BarStyle {
+        match self {
+            Tier::Ascii => BarStyle::ASCII,
+            _ => BarStyle::BLOCKS,
+        }
+    }
+}
+
+struct ThemedBar<'a> {
+    theme: Theme,
+    bar: &'a Bar,
+}
+
+impl<'a> Display for ThemedBar<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = f.width().unwrap_or(80) as u32;
+        let (full, head, empty, pad) = self.bar.segments(width);
+        let style = self.bar.style();
+        if full > 0 {
+            self.theme.filled.set(f)?;
+            for _ in 0..full {
+                f.write_str(style.full)?;
+            }
+            self.theme.filled.reset(f)?;
+        }
+        if let Some(head) = head {
+            self.theme.head.set(f)?;
+            f.write_str(head)?;
+            self.theme.head.reset(f)?;
+        }
+        if empty > 0 {
+            self.theme.empty.set(f)?;
+            style.write_empty(f, empty)?;
+            self.theme.empty.reset(f)?;
+        }
+        let fill = f.fill();
+        for _ in 0..pad {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Progress;
+
+    #[test]
+    fn bar() {
+        let mut bar = Bar::new();
+        bar.set(0.5);
+        let theme = Theme::CLASSIC;
+        assert_eq!(
+            format!("{:4}", theme.bar(&bar)),
+            "\x1b[32m██\x1b[39m\x1b[90m  \x1b[39m"
+        );
+        // Without colors, a themed bar is identical to a plain one
+        for &fraction in &[0.0, 0.3, 0.55, 1.0] {
+            bar.set(fraction);
+            assert_eq!(
+                format!("{:-^7}", Theme::MONO.bar(&bar)),
+                format!("{:-^7}", bar)
+            );
+        }
+    }
+
+    #[test]
+    fn palette() {
+        assert_eq!(Color::Ansi(9).rgb(), Some((0xff, 0, 0)));
+        assert_eq!(Color::Indexed(16).rgb(), Some((0, 0, 0)));
+        assert_eq!(Color::Indexed(231).rgb(), Some((255, 255, 255)));
+        assert_eq!(Color::Indexed(255).rgb(), Some((238, 238, 238)));
+        assert_eq!(Color::Ansi(12).paint(1).to_string(), "\x1b[94m1\x1b[39m");
+        assert_eq!(Theme::preset("ocean"), Some(Theme::OCEAN));
+        assert_eq!(Theme::preset("bogus"), None);
+    }
+
+    #[test]
+    fn tiers() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|&&(x, _)| x == name)
+                    .map(|&(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(Tier::from_env(true, env(&[])), Tier::Mono);
+        assert_eq!(Tier::from_env(true, env(&[("TERM", "dumb")])), Tier::Ascii);
+        assert_eq!(
+            Tier::from_env(true, env(&[("TERM", "xterm")])),
+            Tier::Ansi16
+        );
+        assert_eq!(
+            Tier::from_env(true, env(&[("TERM", "xterm"), ("COLORTERM", "truecolor")])),
+            Tier::TrueColor
+        );
+        assert_eq!(
+            Tier::from_env(true, env(&[("TERM", "xterm"), ("NO_COLOR", "1")])),
+            Tier::Mono
+        );
+        assert_eq!(
+            Tier::from_env(true, env(&[("TERM", "xterm"), ("NO_COLOR", "")])),
+            Tier::Ansi16
+        );
+        assert_eq!(Theme::OCEAN.degrade(Tier::Mono), Theme::MONO);
+        assert_eq!(Theme::CLASSIC.degrade(Tier::Ansi16), Theme::CLASSIC);
+        assert_eq!(Color::Indexed(244).degrade(Tier::Ansi16), Color::Ansi(8));
+    }
+}
@@ -0,0 +1,144 @@
+//! Progress recorded on `tracing` spans
+//!
+//! Instrumented code can report progress as fields of the span describing the work, so subscribers see it alongside
+//! the span's other data while the work is in flight.
+
+extern crate tracing;
+
+use std::fmt::{self, Display};
+
+use self::tracing::Span;
+
+use {Progress, WidthHint};
+
+/// Records progress on a span as it's set on an inner indicator
+///
+/// The span must declare the fields it will receive, since `tracing` ignores fields unknown at creation: `fraction`,
+/// set by `Progress::set`, and `rate`, set by `record_rate`. Display is delegated to the inner indicator.
+///
+/// # Examples
+/// ```
+/// # extern crate tracing;
+/// # extern crate yapb;
+/// # use tracing::field::Empty;
+/// # use yapb::*;
+/// # use yapb::tracing::Instrumented;
+/// # fn main() {
+/// let span = tracing::info_span!("download", fraction = Empty, rate = Empty);
+/// let mut bar = Instrumented::new(Bar::new(), span);
+/// bar.set(0.5);
+/// bar.record_rate(1024.0);
+/// assert_eq!(format!("{:4}", bar), "██  ");
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Instrumented<P> {
+    inner: P,
+    span: Span,
+}
+
+impl<P> Instrumented<P> {
+    pub fn new(inner: P, span: Span) -> Self {
+        Self { inner, span }
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Record the rate of progress, in units of the caller's choosing per second, as the span's `rate` field
+    pub fn record_rate(&self, rate: f64) {
+        self.span.record("rate", rate);
+    }
+
+    pub fn get_ref(&self) -> &P {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut P {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: Progress> Progress for Instrumented<P> {
+    fn set(&mut self, value: f32) {
+        self.span.record("fraction", f64::from(value));
+        self.inner.set(value);
+    }
+}
+
+impl<P: Display> Display for Instrumented<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<P: WidthHint> WidthHint for Instrumented<P> {
+    fn width_hint(&self, width: Option<usize>) -> usize {
+        self.inner.width_hint(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tracing::field::{Empty, Field, Visit};
+    use super::tracing::span::{Attributes, Id, Record};
+    use super::tracing::{Event, Metadata, Subscriber};
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use Bar;
+
+    /// Collects the values recorded on spans
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<(&'static str, f64)>>>);
+
+    impl Visit for Capture {
+        fn record_f64(&mut self, field: &Field, value: f64) {
+            self.0.lock().unwrap().push((field.name(), value));
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn records() {
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            let span = tracing::info_span!("copy", fraction = Empty, rate = Empty);
+            let mut bar = Instrumented::new(Bar::new(), span);
+            bar.set(0.25);
+            bar.record_rate(10.0);
+            bar.set(0.5);
+        });
+        assert_eq!(
+            *capture.0.lock().unwrap(),
+            [("fraction", 0.25), ("rate", 10.0), ("fraction", 0.5)]
+        );
+    }
+}
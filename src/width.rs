@@ -0,0 +1,244 @@
+//! Estimates of the number of terminal cells occupied by text
+//!
+//! These follow the common terminal behavior of treating East Asian wide characters and emoji as two cells and
+//! combining marks as zero, without depending on the full Unicode tables. Terminals themselves disagree on many edge
+//! cases, so this is necessarily an approximation.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Alignment, Write};
+
+/// Number of terminal cells occupied by `c` when it is rendered in isolation
+pub fn char_width(c: char) -> usize {
+    let c = c as u32;
+    if in_table(c, ZERO_WIDTH) {
+        0
+    } else if in_table(c, WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Number of terminal cells occupied by `s`
+///
+/// An emoji presentation selector (U+FE0F) widens the preceding character to two cells.
+pub fn str_width(s: &str) -> usize {
+    let mut total = 0;
+    let mut last = 0;
+    for c in s.chars() {
+        if c == '\u{FE0F}' && last == 1 {
+            total += 1;
+            last = 2;
+            continue;
+        }
+        last = char_width(c);
+        total += last;
+    }
+    total
+}
+
+/// Write `s` to `f`, honoring the formatter's width, fill, and alignment as measured in terminal cells
+///
+/// `Formatter::pad` counts `char`s, which misaligns surrounding columns when `s` contains wide characters.
+pub fn pad(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let width = str_width(s);
+    let padding = match f.width() {
+        Some(target) if target > width => target - width,
+        _ => return f.write_str(s),
+    };
+    let (before, after) = match f.align() {
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// The longest prefix of `s` that occupies at most `cells` terminal cells
+pub fn truncate(s: &str, cells: usize) -> &str {
+    let mut total = 0;
+    for (i, c) in s.char_indices() {
+        total += char_width(c);
+        if total > cells {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+/// Split `s` into approximate grapheme clusters, each paired with its width in cells
+///
+/// A cluster is a character followed by any zero-width characters, with zero-width joiners also absorbing the
+/// character after them. This keeps combining marks, variation selectors, and most emoji sequences intact.
+pub fn clusters(s: &str) -> Clusters<'_> {
+    Clusters { rest: s }
+}
+
+pub struct Clusters<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Clusters<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<(&'a str, usize)> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut end = first.len_utf8();
+        let mut joined = false;
+        for (i, c) in chars {
+            if !joined && char_width(c) != 0 {
+                break;
+            }
+            joined = c == '\u{200D}';
+            end = i + c.len_utf8();
+        }
+        let (cluster, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some((cluster, str_width(cluster)))
+    }
+}
+
+/// Characters that occupy no cells of their own, such as combining marks and selectors
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0000, 0x001F),
+    (0x007F, 0x009F),
+    (0x0300, 0x036F),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0100, 0xE01EF),
+];
+
+/// East Asian wide and fullwidth characters, and emoji with default emoji presentation
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE30, 0xFE4F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F7F0, 0x1F7F0),
+    (0x1F900, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x3FFFD),
+];
+
+/// Whether `c` falls within any of the sorted, disjoint, inclusive `ranges`
+fn in_table(c: u32, ranges: &[(u32, u32)]) -> bool {
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                Ordering::Less
+            } else if lo > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths() {
+        assert_eq!(str_width("abc"), 3);
+        assert_eq!(str_width("⠋█▌"), 3);
+        assert_eq!(str_width("🌑"), 2);
+        assert_eq!(str_width("進捗"), 4);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("☀\u{FE0F}"), 2);
+    }
+
+    #[test]
+    fn truncation() {
+        assert_eq!(truncate("abc", 2), "ab");
+        assert_eq!(truncate("進捗", 3), "進");
+        assert_eq!(truncate("abc", 5), "abc");
+    }
+
+    #[test]
+    fn cluster_split() {
+        assert_eq!(
+            clusters("e\u{301}進☀\u{FE0F}👩\u{200D}🔬").collect::<Vec<_>>(),
+            [
+                ("e\u{301}", 1),
+                ("進", 2),
+                ("☀\u{FE0F}", 2),
+                ("👩\u{200D}🔬", 4)
+            ]
+        );
+    }
+
+    struct Padded(&'static str);
+    impl fmt::Display for Padded {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            pad(f, self.0)
+        }
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(format!("[{:4}]", Padded("🌑")), "[🌑  ]");
+        assert_eq!(format!("[{:>4}]", Padded("🌑")), "[  🌑]");
+        assert_eq!(format!("[{:-^5}]", Padded("🌑")), "[-🌑--]");
+        assert_eq!(format!("[{:1}]", Padded("🌑")), "[🌑]");
+    }
+}